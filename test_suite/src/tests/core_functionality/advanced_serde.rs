@@ -9,6 +9,19 @@ struct XmlObjectWithInner {
     pub metadata: Option<String>,
 }
 
+// Same layout as XmlObjectWithInner for values already encoded with encode!
+#[derive(XmlSerializable)]
+#[xml(name="XmlObjectWithInner")]
+struct XmlObjectEncoded {
+    pub id: u32,
+    #[xml(raw)]
+    pub name: String,
+    #[xml(inner="item", raw)]
+    pub items: Vec<String>,
+    #[xml(raw)]
+    pub metadata: Option<String>,
+}
+
 #[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
 struct XmlObjectComplex {
     pub id: u32,
@@ -144,7 +157,7 @@ fn advanced_round_trip() -> Result<(), PError> {
 #[test]
 fn advanced_encoding() -> Result<(), PError> {
 
-    let test_data = XmlObjectWithInner {
+    let test_data = XmlObjectEncoded {
         id: 789,
         name: encode!("Test with special chars: & < > \" '"),
         items: vec![
@@ -0,0 +1,98 @@
+use xavier::{from_obj, from_xml, PError, XmlDeserializable, XmlSerializable};
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="customer", alias="client", alias="Client")]
+struct Customer {
+    #[xml(attribute, name="id", alias="customerId")]
+    pub id: u32,
+    #[xml(name="fullName", alias="name", alias="customerName")]
+    pub full_name: String,
+    #[xml(alias="addr")]
+    pub address: Option<Address>,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="address", alias="location")]
+struct Address {
+    pub city: String,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="branch")]
+struct Branch {
+    #[xml(tree)]
+    pub address: Address,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="visits")]
+struct Visits {
+    #[xml(flatten)]
+    pub places: Vec<Address>,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+enum Status {
+    #[xml(alias="enabled", alias="on")]
+    Active,
+    Inactive,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="shape")]
+enum Shape {
+    #[xml(name="circle", alias="round")]
+    Circle { radius: u32 },
+    #[xml(name="square", alias="box")]
+    Square(u32),
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="account")]
+struct Account {
+    pub status: Status,
+    pub shape: Shape,
+}
+
+#[test]
+fn fields_accept_their_aliases() -> Result<(), PError> {
+    let expected = Customer {
+        id: 7,
+        full_name: "Ana".to_string(),
+        address: Some(Address { city: "Lisbon".to_string() }),
+    };
+    let old: Customer = from_xml(r#"<client customerId="7"><customerName>Ana</customerName><addr><address><city>Lisbon</city></address></addr></client>"#)?;
+    assert_eq!(old, expected);
+    let older: Customer = from_xml(r#"<Client id="7"><name>Ana</name><address><location><city>Lisbon</city></location></address></Client>"#)?;
+    assert_eq!(older, expected);
+
+    // Serialization always uses the primary names
+    assert_eq!(from_obj(&expected), r#"<customer id="7"><fullName>Ana</fullName><address><address><city>Lisbon</city></address></address></customer>"#);
+    Ok(())
+}
+
+#[test]
+fn tree_fields_accept_root_aliases() -> Result<(), PError> {
+    let branch: Branch = from_xml("<branch><location><city>Porto</city></location></branch>")?;
+    assert_eq!(branch.address.city, "Porto");
+    assert_eq!(from_obj(&branch), "<branch><address><city>Porto</city></address></branch>");
+    Ok(())
+}
+
+#[test]
+fn flattened_items_accept_root_aliases() -> Result<(), PError> {
+    let visits: Visits = from_xml("<visits><address><city>A</city></address><location><city>B</city></location></visits>")?;
+    assert_eq!(visits.places.iter().map(|place| place.city.as_str()).collect::<Vec<_>>(), vec!["A", "B"]);
+    Ok(())
+}
+
+#[test]
+fn variants_accept_their_aliases() -> Result<(), PError> {
+    let account: Account = from_xml("<account><status>on</status><shape><round><radius>2</radius></round></shape></account>")?;
+    assert_eq!(account, Account { status: Status::Active, shape: Shape::Circle { radius: 2 } });
+
+    let account: Account = from_xml("<account><status>Inactive</status><shape><box>3</box></shape></account>")?;
+    assert_eq!(account.shape, Shape::Square(3));
+    assert_eq!(from_obj(&account), "<account><status>Inactive</status><shape><square>3</square></shape></account>");
+    Ok(())
+}
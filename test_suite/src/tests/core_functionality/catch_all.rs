@@ -0,0 +1,70 @@
+use xavier::dom::{Element, XmlNode};
+use xavier::{from_obj, from_xml, PError, XmlDeserializable, XmlSerializable};
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="invoice")]
+struct Invoice {
+    #[xml(attribute)]
+    pub id: u32,
+    #[xml(other_attributes)]
+    pub extra_attrs: Vec<(String, String)>,
+    pub total: f64,
+    #[xml(other_elements)]
+    pub extra: Vec<XmlNode>,
+    pub status: String,
+}
+
+#[test]
+fn unknown_content_is_kept() -> Result<(), PError> {
+    let xml = r#"<invoice id="1" vendor="acme" note="a &amp; b"><total>10.5</total><lines><line sku="x">2 &lt; 3</line><!--kept--><line sku="y"><![CDATA[<raw>]]></line></lines><empty/><status>open</status></invoice>"#;
+    let mut invoice: Invoice = from_xml(xml)?;
+    assert_eq!(invoice.id, 1);
+    assert_eq!(invoice.extra_attrs, vec![
+        ("vendor".to_string(), "acme".to_string()),
+        ("note".to_string(), "a & b".to_string()),
+    ]);
+    assert_eq!(invoice.extra.len(), 2);
+
+    let XmlNode::Element(lines) = &invoice.extra[0] else { panic!("lines should be an element") };
+    assert_eq!(lines.name, "lines");
+    assert_eq!(lines.children.len(), 3);
+    assert_eq!(lines.children[1], XmlNode::Comment("kept".to_string()));
+
+    // Captured content goes back where the field is
+    invoice.total = 12.0;
+    invoice.status = "paid".to_string();
+    assert_eq!(from_obj(&invoice), r#"<invoice id="1" vendor="acme" note="a &amp; b"><total>12</total><lines><line sku="x">2 &lt; 3</line><!--kept--><line sku="y"><![CDATA[<raw>]]></line></lines><empty/><status>paid</status></invoice>"#);
+    Ok(())
+}
+
+#[test]
+fn nothing_to_capture() -> Result<(), PError> {
+    let invoice: Invoice = from_xml(r#"<invoice id="2"><total>1</total><status>open</status></invoice>"#)?;
+    assert!(invoice.extra.is_empty());
+    assert!(invoice.extra_attrs.is_empty());
+
+    let mut element = Element::new("note");
+    element.children.push(XmlNode::Text("hi".to_string()));
+    let invoice = Invoice { extra: vec![XmlNode::Element(element)], ..invoice };
+    assert_eq!(from_obj(&invoice), r#"<invoice id="2"><total>1</total><note>hi</note><status>open</status></invoice>"#);
+    Ok(())
+}
+
+#[test]
+fn comments_pis_and_text_are_captured() -> Result<(), PError> {
+    let xml = "<invoice id=\"3\"><!--head--><total>4</total>\n  <?audit by=\"x\"?>loose &amp; text<![CDATA[<c>]]><b>bold</b>\n<status>open</status></invoice>";
+    let invoice: Invoice = from_xml(xml)?;
+    // Whitespace between the children is layout and isn't kept
+    assert_eq!(invoice.extra, vec![
+        XmlNode::Comment("head".to_string()),
+        XmlNode::PI("audit by=\"x\"".to_string()),
+        XmlNode::Text("loose & text".to_string()),
+        XmlNode::CData("<c>".to_string()),
+        XmlNode::Element(Element::new("b").with_text("bold")),
+    ]);
+
+    // Document order is kept among the captured nodes, they are written where the field is
+    assert_eq!(from_obj(&invoice), r#"<invoice id="3"><total>4</total><!--head--><?audit by="x"?>loose &amp; text<![CDATA[<c>]]><b>bold</b><status>open</status></invoice>"#);
+    assert_eq!(from_xml::<Invoice>(&from_obj(&invoice))?, invoice);
+    Ok(())
+}
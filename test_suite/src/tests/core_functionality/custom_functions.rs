@@ -0,0 +1,95 @@
+use std::time::Duration;
+use xavier::{from_obj, from_xml, PError, XmlDeserializable, XmlSerializable};
+
+mod millis {
+    use std::time::Duration;
+    use xavier::PError;
+
+    pub fn serialize(value: &Duration) -> String {
+        format!("{}ms", value.as_millis())
+    }
+
+    pub fn deserialize(text: &str) -> Result<Duration, PError> {
+        let millis = text.trim_end_matches("ms").parse::<u64>()?;
+        Ok(Duration::from_millis(millis))
+    }
+}
+
+fn hex(value: &u32) -> String {
+    format!("{:#x}", value)
+}
+
+fn from_hex(text: &str) -> Result<u32, std::num::ParseIntError> {
+    u32::from_str_radix(text.trim_start_matches("0x"), 16)
+}
+
+fn joined(values: &[String]) -> String {
+    values.join(",")
+}
+
+fn split(text: &str) -> Result<Vec<String>, PError> {
+    Ok(text.split(',').map(|value| value.to_string()).collect())
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="job")]
+struct Job {
+    #[xml(attribute, serialize_with="hex", deserialize_with="from_hex")]
+    pub flags: u32,
+    #[xml(attribute, with="millis")]
+    pub delay: Option<Duration>,
+    #[xml(with="millis")]
+    pub timeout: Duration,
+    #[xml(with="millis")]
+    pub retry: Option<Duration>,
+    #[xml(serialize_with="joined", deserialize_with="split")]
+    pub tags: Vec<String>,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="wait")]
+struct Wait {
+    #[xml(attribute)]
+    pub id: u32,
+    #[xml(value, with="millis")]
+    pub time: Duration,
+}
+
+#[test]
+fn with_functions_on_elements_and_attributes() -> Result<(), PError> {
+    let job = Job {
+        flags: 255,
+        delay: Some(Duration::from_millis(15)),
+        timeout: Duration::from_secs(2),
+        retry: None,
+        tags: vec!["a".to_string(), "b".to_string()],
+    };
+    let xml = from_obj(&job);
+    assert_eq!(xml, r#"<job flags="0xff" delay="15ms"><timeout>2000ms</timeout><tags>a,b</tags></job>"#);
+    assert_eq!(from_xml::<Job>(&xml)?, job);
+
+    let job: Job = from_xml(r#"<job flags="0x10"><timeout>5ms</timeout><retry>7ms</retry><tags>x</tags></job>"#)?;
+    assert_eq!(job.flags, 16);
+    assert_eq!(job.delay, None);
+    assert_eq!(job.retry, Some(Duration::from_millis(7)));
+    assert_eq!(job.tags, vec!["x".to_string()]);
+    Ok(())
+}
+
+#[test]
+fn with_functions_on_value_fields() -> Result<(), PError> {
+    let wait = Wait { id: 1, time: Duration::from_millis(250) };
+    let xml = from_obj(&wait);
+    assert_eq!(xml, r#"<wait id="1">250ms</wait>"#);
+    assert_eq!(from_xml::<Wait>(&xml)?, wait);
+    Ok(())
+}
+
+#[test]
+fn with_function_errors_are_reported() {
+    let error = from_xml::<Job>(r#"<job flags="zz"><timeout>1ms</timeout><tags>x</tags></job>"#).unwrap_err();
+    assert!(error.to_string().contains("invalid digit"));
+
+    let error = from_xml::<Job>(r#"<job flags="0x1"><timeout>soon</timeout><tags>x</tags></job>"#).unwrap_err();
+    assert!(error.to_string().contains("invalid digit"));
+}
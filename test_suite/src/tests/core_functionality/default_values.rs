@@ -0,0 +1,100 @@
+use xavier::{from_xml, PError, XmlDeserializable};
+
+fn default_port() -> u16 {
+    8080
+}
+
+fn default_host() -> String {
+    "localhost".to_string()
+}
+
+#[derive(XmlDeserializable, Debug, PartialEq)]
+#[xml(name="server")]
+struct Server {
+    #[xml(attribute, default)]
+    pub enabled: bool,
+    #[xml(attribute, default="default_port")]
+    pub port: u16,
+    #[xml(default="default_host")]
+    pub host: String,
+    #[xml(default)]
+    pub aliases: Vec<String>,
+    #[xml(default)]
+    pub retries: u32,
+    pub name: String,
+}
+
+#[derive(XmlDeserializable, Debug, PartialEq)]
+#[xml(name="label")]
+struct Label {
+    #[xml(attribute)]
+    pub id: u32,
+    #[xml(value, default)]
+    pub text: String,
+}
+
+#[derive(XmlDeserializable, Debug, PartialEq)]
+#[xml(name="limits", default)]
+struct Limits {
+    pub min: i32,
+    pub max: i32,
+    #[xml(default="default_port")]
+    pub port: u16,
+    pub note: Option<String>,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits { min: -1, max: 100, port: 0, note: Some("default".to_string()) }
+    }
+}
+
+#[test]
+fn missing_fields_use_their_defaults() -> Result<(), PError> {
+    let server: Server = from_xml("<server><name>api</name></server>")?;
+    assert_eq!(server, Server {
+        enabled: false,
+        port: 8080,
+        host: "localhost".to_string(),
+        aliases: vec![],
+        retries: 0,
+        name: "api".to_string(),
+    });
+
+    let server: Server = from_xml(r#"<server enabled="true" port="9"><host>h</host><retries>3</retries><name>api</name></server>"#)?;
+    assert!(server.enabled);
+    assert_eq!(server.port, 9);
+    assert_eq!(server.host, "h");
+    assert_eq!(server.retries, 3);
+    Ok(())
+}
+
+#[test]
+fn fields_without_default_are_still_required() {
+    let error = from_xml::<Server>("<server></server>").unwrap_err();
+    assert!(error.to_string().contains("Field value 'name' not found"));
+}
+
+#[test]
+fn present_but_invalid_values_are_not_defaulted() {
+    let error = from_xml::<Server>(r#"<server port="x"><name>api</name></server>"#).unwrap_err();
+    assert!(error.to_string().contains("invalid digit"));
+}
+
+#[test]
+fn value_fields_use_their_defaults() -> Result<(), PError> {
+    let label: Label = from_xml(r#"<label id="1"></label>"#)?;
+    assert_eq!(label, Label { id: 1, text: "".to_string() });
+    Ok(())
+}
+
+#[test]
+fn container_default_fills_missing_fields() -> Result<(), PError> {
+    let limits: Limits = from_xml("<limits><max>5</max></limits>")?;
+    assert_eq!(limits, Limits { min: -1, max: 5, port: 8080, note: Some("default".to_string()) });
+
+    let limits: Limits = from_xml("<limits><note>set</note></limits>")?;
+    assert_eq!(limits.note.as_deref(), Some("set"));
+    assert_eq!(limits.min, -1);
+    Ok(())
+}
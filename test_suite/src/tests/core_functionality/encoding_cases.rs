@@ -14,4 +14,13 @@ fn decode_test() {
     let encoded = "Test &amp; &gt; &lt;";
     let decoded = "Test & > <";
     assert_eq!(decoded, decode!(encoded));
-}
\ No newline at end of file
+}
+
+#[test]
+fn decode_character_references() {
+    assert_eq!(decode!("caf&#233; &#x20AC;5 &#X41; &#65;"), "café €5 &#X41; A");
+    // Single pass, an escaped reference stays escaped
+    assert_eq!(decode!("&amp;lt; &amp;amp;"), "&lt; &amp;");
+    // Malformed, unknown and invalid references are kept as they are
+    assert_eq!(decode!("AT&T &nbsp; &#xD800; &#0; &#x110000; &#-1; &#x; a & b"), "AT&T &nbsp; &#xD800; &#0; &#x110000; &#-1; &#x; a & b");
+}
@@ -0,0 +1,108 @@
+use std::io::{BufReader, Read};
+use xavier::{from_reader, from_slice, to_bytes, PError, XmlDeserializable, XmlSerializable};
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[declaration(version="1.0", encoding="UTF-16")]
+#[xml(name="doc")]
+struct Utf16Doc {
+    #[xml(attribute)]
+    pub lang: String,
+    pub text: String,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[declaration(version="1.0", encoding="ISO-8859-1")]
+#[xml(name="doc")]
+struct Latin1Doc {
+    #[xml(attribute)]
+    pub lang: String,
+    pub text: String,
+}
+
+// Hands out a single byte per read so the encoding has to be detected across reads
+struct TrickleReader<'a> {
+    data: &'a [u8],
+}
+
+impl Read for TrickleReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let size = buf.len().min(self.data.len()).min(1);
+        buf[..size].copy_from_slice(&self.data[..size]);
+        self.data = &self.data[size..];
+        Ok(size)
+    }
+}
+
+fn utf16(xml: &str, big_endian: bool, bom: bool) -> Vec<u8> {
+    let mut bytes = vec![];
+    if bom {
+        bytes.extend_from_slice(&if big_endian { [0xFE, 0xFF] } else { [0xFF, 0xFE] });
+    }
+    for unit in xml.encode_utf16() {
+        bytes.extend_from_slice(&if big_endian { unit.to_be_bytes() } else { unit.to_le_bytes() });
+    }
+    bytes
+}
+
+#[test]
+fn deserialize_utf16_with_bom() -> Result<(), PError> {
+    let xml = r#"<?xml version="1.0" encoding="UTF-16"?><doc lang="pt"><text>Olá 😀</text></doc>"#;
+    let should = Utf16Doc { lang: "pt".to_string(), text: "Olá 😀".to_string() };
+    assert_eq!(from_slice::<Utf16Doc>(&utf16(xml, false, true))?, should);
+    assert_eq!(from_slice::<Utf16Doc>(&utf16(xml, true, true))?, should);
+    Ok(())
+}
+
+#[test]
+fn deserialize_utf16_without_bom() -> Result<(), PError> {
+    let xml = r#"<?xml version="1.0" encoding="UTF-16"?><doc lang="pt"><text>Olá</text></doc>"#;
+    let obj: Utf16Doc = from_slice(&utf16(xml, true, false))?;
+    assert_eq!(obj.text, "Olá");
+    Ok(())
+}
+
+#[test]
+fn deserialize_latin1_from_declaration() -> Result<(), PError> {
+    let mut bytes = b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?><doc lang=\"fr\"><text>caf".to_vec();
+    bytes.push(0xE9);
+    bytes.extend_from_slice(b"</text></doc>");
+    let obj: Latin1Doc = from_reader(BufReader::new(TrickleReader { data: &bytes }))?;
+    assert_eq!(obj, Latin1Doc { lang: "fr".to_string(), text: "café".to_string() });
+    Ok(())
+}
+
+#[test]
+fn deserialize_utf8_bom() -> Result<(), PError> {
+    let mut bytes = vec![0xEF, 0xBB, 0xBF];
+    bytes.extend_from_slice("<doc lang=\"pt\"><text>Olá</text></doc>".as_bytes());
+    let obj: Utf16Doc = from_slice(&bytes)?;
+    assert_eq!(obj.text, "Olá");
+    Ok(())
+}
+
+#[test]
+fn deserialize_unknown_encoding_fails() {
+    let xml = b"<?xml version=\"1.0\" encoding=\"EBCDIC-42\"?><doc lang=\"pt\"><text>x</text></doc>";
+    assert!(from_slice::<Latin1Doc>(xml).is_err());
+}
+
+#[test]
+fn encoded_round_trip() -> Result<(), PError> {
+    let utf16 = Utf16Doc { lang: "ja".to_string(), text: "日本語 & more".to_string() };
+    assert_eq!(from_slice::<Utf16Doc>(&to_bytes(&utf16)?)?, utf16);
+
+    let latin1 = Latin1Doc { lang: "fr".to_string(), text: "Crème brûlée à 20".to_string() };
+    assert_eq!(from_slice::<Latin1Doc>(&to_bytes(&latin1)?)?, latin1);
+    Ok(())
+}
+
+#[test]
+fn latin1_round_trip_keeps_c1_range() -> Result<(), PError> {
+    let text: String = (0x80u8..=0x9F).map(char::from).collect();
+    let obj = Latin1Doc { lang: text.clone(), text };
+    let bytes = to_bytes(&obj)?;
+    assert!(bytes.windows(32).any(|window| window.iter().copied().eq(0x80u8..=0x9F)));
+    assert_eq!(from_slice::<Latin1Doc>(&bytes)?, obj);
+    assert_eq!(from_reader::<Latin1Doc, _>(BufReader::new(TrickleReader { data: &bytes }))?, obj);
+    Ok(())
+}
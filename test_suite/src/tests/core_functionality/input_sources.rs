@@ -0,0 +1,106 @@
+use std::fs;
+use std::io::{BufRead, BufReader, Cursor, Read};
+use xavier::{from_file, from_reader, from_reader_using_builder, from_slice, from_xml, PError, XmlDeserializable};
+
+#[derive(XmlDeserializable, Debug, PartialEq)]
+#[xml(name="item")]
+struct Item {
+    #[xml(attribute)]
+    pub id: u32,
+    pub label: String,
+}
+
+#[derive(XmlDeserializable, Debug, PartialEq)]
+struct XMLObject {
+    pub title: String,
+    pub items: Vec<Item>,
+    pub count: i32,
+}
+
+const XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<XMLObject>
+    <title>Sources</title>
+    <items>
+        <item id="1"><label>First</label></item>
+        <item id="2"><label>Second</label></item>
+    </items>
+    <count>2</count>
+</XMLObject>"#;
+
+// Hands out at most a few bytes per read to force incremental parsing
+struct TrickleReader<'a> {
+    data: &'a [u8],
+}
+
+impl Read for TrickleReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let size = buf.len().min(self.data.len()).min(3);
+        buf[..size].copy_from_slice(&self.data[..size]);
+        self.data = &self.data[size..];
+        Ok(size)
+    }
+}
+
+fn check(obj: &XMLObject) {
+    assert_eq!(obj.title, "Sources");
+    assert_eq!(obj.items.len(), 2);
+    assert_eq!(obj.items[1], Item { id: 2, label: "Second".to_string() });
+    assert_eq!(obj.count, 2);
+}
+
+#[test]
+fn deserialize_from_slice() -> Result<(), PError> {
+    let obj: XMLObject = from_slice(XML.as_bytes())?;
+    check(&obj);
+    assert_eq!(obj, from_xml(XML)?);
+    Ok(())
+}
+
+#[test]
+fn deserialize_from_reader() -> Result<(), PError> {
+    let obj: XMLObject = from_reader(Cursor::new(XML.as_bytes()))?;
+    check(&obj);
+    Ok(())
+}
+
+#[test]
+fn deserialize_from_reader_incrementally() -> Result<(), PError> {
+    let reader = BufReader::with_capacity(4, TrickleReader { data: XML.as_bytes() });
+    let obj: XMLObject = from_reader(reader)?;
+    check(&obj);
+    Ok(())
+}
+
+#[test]
+fn deserialize_from_any_buf_read() -> Result<(), PError> {
+    let boxed: Box<dyn BufRead> = Box::new(Cursor::new(XML.as_bytes()));
+    let obj = from_reader_using_builder(boxed, XMLObject::from_xml)?.unwrap();
+    check(&obj);
+
+    let mut cursor = Cursor::new(XML.as_bytes());
+    let obj = from_reader_using_builder(&mut cursor, XMLObject::from_xml)?.unwrap();
+    check(&obj);
+    Ok(())
+}
+
+#[test]
+fn deserialize_from_file() -> Result<(), PError> {
+    let path = std::env::temp_dir().join(format!("xavier_input_sources_{}.xml", std::process::id()));
+    fs::write(&path, XML)?;
+    let result: Result<XMLObject, PError> = from_file(&path);
+    fs::remove_file(&path)?;
+    check(&result?);
+    Ok(())
+}
+
+#[test]
+fn deserialize_from_missing_file() {
+    let result: Result<XMLObject, PError> = from_file("/this/file/does/not/exist.xml");
+    assert!(result.is_err());
+}
+
+#[test]
+fn deserialize_from_empty_sources() {
+    assert!(from_slice::<XMLObject>(b"  \n ").is_err());
+    assert!(from_reader::<XMLObject, _>(Cursor::new(Vec::new())).is_err());
+}
@@ -0,0 +1,108 @@
+use xavier::{from_obj, from_obj_pretty, from_xml, PError, XmlDeserializable, XmlSerializable};
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="inline")]
+enum Inline {
+    #[xml(text)]
+    Text(String),
+    #[xml(name="b")]
+    Bold(String),
+    #[xml(name="a")]
+    Link {
+        #[xml(attribute)]
+        href: String,
+        #[xml(value)]
+        label: String,
+    },
+    #[xml(tree)]
+    Emphasis(Emphasis),
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="em")]
+struct Emphasis {
+    #[xml(mixed)]
+    pub content: Vec<Inline>,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="p")]
+struct Paragraph {
+    #[xml(attribute)]
+    pub id: Option<String>,
+    #[xml(mixed)]
+    pub content: Vec<Inline>,
+}
+
+fn text(value: &str) -> Inline {
+    Inline::Text(value.to_string())
+}
+
+#[test]
+fn text_and_elements_keep_their_order() -> Result<(), PError> {
+    let xml = r#"<p id="intro">Hello <b>world</b>, see <a href="/docs">the docs</a> &amp; <em>more <b>bold</b></em>!</p>"#;
+    let paragraph: Paragraph = from_xml(xml)?;
+    assert_eq!(paragraph.id.as_deref(), Some("intro"));
+    assert_eq!(paragraph.content, vec![
+        text("Hello "),
+        Inline::Bold("world".to_string()),
+        text(", see "),
+        Inline::Link { href: "/docs".to_string(), label: "the docs".to_string() },
+        text(" & "),
+        Inline::Emphasis(Emphasis { content: vec![text("more "), Inline::Bold("bold".to_string())] }),
+        text("!"),
+    ]);
+    assert_eq!(from_obj(&paragraph), xml);
+    Ok(())
+}
+
+#[test]
+fn mixed_content_edge_cases() -> Result<(), PError> {
+    let paragraph: Paragraph = from_xml("<p></p>")?;
+    assert!(paragraph.content.is_empty());
+
+    let paragraph: Paragraph = from_xml("<p><![CDATA[1 < 2]]> <b>ok</b></p>")?;
+    assert_eq!(paragraph.content, vec![text("1 < 2"), text(" "), Inline::Bold("ok".to_string())]);
+
+    let paragraph = Paragraph { id: None, content: vec![text("a < b "), Inline::Bold("c".to_string())] };
+    assert_eq!(from_obj(&paragraph), "<p>a &lt; b <b>c</b></p>");
+    Ok(())
+}
+
+#[test]
+fn pretty_output_keeps_mixed_content() -> Result<(), PError> {
+    let paragraph = Paragraph { id: None, content: vec![
+        Inline::Bold("a".to_string()),
+        Inline::Emphasis(Emphasis { content: vec![Inline::Bold("b".to_string()), text(" c")] }),
+    ] };
+    let xml = from_obj_pretty(&paragraph);
+    assert!(xml.ends_with("<p><b>a</b><em><b>b</b> c</em></p>"));
+    assert_eq!(from_xml::<Paragraph>(&xml)?, paragraph);
+    Ok(())
+}
+
+#[derive(XmlDeserializable, Debug, PartialEq)]
+#[xml(name="score")]
+enum Score {
+    #[xml(text)]
+    Points(u32),
+    #[xml(name="bonus")]
+    Bonus(u32),
+}
+
+#[derive(XmlDeserializable, Debug, PartialEq)]
+#[xml(name="scores")]
+struct Scores {
+    #[xml(mixed)]
+    pub items: Vec<Score>,
+}
+
+#[test]
+fn mixed_text_parse_errors_are_reported() -> Result<(), PError> {
+    let scores: Scores = from_xml("<scores>12<bonus>3</bonus>4</scores>")?;
+    assert_eq!(scores.items, vec![Score::Points(12), Score::Bonus(3), Score::Points(4)]);
+
+    let result: Result<Scores, PError> = from_xml("<scores>12<bonus>3</bonus>abc</scores>");
+    assert!(result.is_err());
+    Ok(())
+}
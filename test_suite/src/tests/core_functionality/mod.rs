@@ -1,4 +1,18 @@
 pub mod serialization;
 pub mod deserialization;
 pub mod encoding_cases;
-pub mod advanced_serde;
\ No newline at end of file
+pub mod advanced_serde;
+pub mod streaming;
+pub mod input_sources;
+pub mod pretty_print;
+pub mod text_escaping;
+pub mod output_encoding;
+pub mod input_encoding;
+pub mod custom_functions;
+pub mod default_values;
+pub mod skip_fields;
+pub mod aliases;
+pub mod catch_all;
+pub mod mixed_content;
+pub mod text_chunks;
+pub mod text_decoding;
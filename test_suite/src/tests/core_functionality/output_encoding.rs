@@ -0,0 +1,117 @@
+use xavier::{cdata, from_obj, to_bytes, to_writer, XmlSerializable};
+
+#[derive(XmlSerializable)]
+#[declaration(version="1.0", encoding="UTF-16")]
+#[xml(name="doc")]
+struct Utf16Doc {
+    pub text: String,
+}
+
+#[derive(XmlSerializable)]
+#[declaration(version="1.0", encoding="ISO-8859-1")]
+#[xml(name="doc")]
+struct Latin1Doc {
+    #[xml(attribute)]
+    pub title: String,
+    pub text: String,
+}
+
+#[derive(XmlSerializable)]
+#[declaration(version="1.0", encoding="Shift_JIS")]
+#[xml(name="doc")]
+struct ShiftJisDoc {
+    pub text: String,
+}
+
+#[derive(XmlSerializable)]
+#[declaration(version="1.0", encoding="EBCDIC-42")]
+#[xml(name="doc")]
+struct UnknownDoc {
+    pub text: String,
+}
+
+#[test]
+fn serialize_utf16_with_bom() -> std::io::Result<()> {
+    let obj = Utf16Doc { text: "Olá 😀".to_string() };
+    let bytes = to_bytes(&obj)?;
+    assert_eq!(&bytes[..2], &[0xFF, 0xFE]);
+
+    let units: Vec<u16> = bytes[2..].chunks(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]])).collect();
+    let should = r#"<?xml version="1.0" encoding="UTF-16" standalone="no"?><doc><text>Olá 😀</text></doc>"#;
+    assert_eq!(String::from_utf16(&units).unwrap(), should);
+    Ok(())
+}
+
+#[test]
+fn serialize_latin1_with_character_references() -> std::io::Result<()> {
+    let obj = Latin1Doc { title: "Œuvre".to_string(), text: "café 20€".to_string() };
+    let mut bytes: Vec<u8> = Vec::new();
+    to_writer(&obj, &mut bytes)?;
+
+    let mut should = b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\" standalone=\"no\"?><doc title=\"&#338;uvre\"><text>caf".to_vec();
+    should.push(0xE9);
+    should.extend_from_slice(b" 20&#8364;</text></doc>");
+    assert_eq!(bytes, should);
+    Ok(())
+}
+
+#[test]
+fn serialize_other_encodings() -> std::io::Result<()> {
+    let bytes = to_bytes(&ShiftJisDoc { text: "日本 ✓".to_string() })?;
+    let mut should = b"<text>".to_vec();
+    should.extend_from_slice(&[0x93, 0xFA, 0x96, 0x7B]);
+    should.extend_from_slice(b" &#10003;</text></doc>");
+    assert!(bytes.ends_with(&should));
+    Ok(())
+}
+
+#[test]
+fn serialize_unknown_encoding_fails() {
+    let error = to_bytes(&UnknownDoc { text: "x".to_string() }).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn serialize_to_string_stays_utf8() {
+    let obj = Latin1Doc { title: "Œuvre".to_string(), text: "café".to_string() };
+    assert!(from_obj(&obj).contains("<doc title=\"Œuvre\"><text>café</text></doc>"));
+}
+
+#[derive(XmlSerializable)]
+#[declaration(version="1.0", encoding="US-ASCII")]
+#[xml(name="doc")]
+struct AsciiDoc {
+    pub text: String,
+    #[xml(raw)]
+    pub fragment: String,
+}
+
+#[derive(XmlSerializable)]
+#[declaration(version="1.0", encoding="US-ASCII")]
+#[xml(name="café")]
+struct AsciiNameDoc {
+    pub text: String,
+}
+
+#[test]
+fn serialize_references_only_in_text_and_attribute_values() -> std::io::Result<()> {
+    let obj = AsciiDoc { text: "é".to_string(), fragment: r#"<p title="é">é<![CDATA[x]]></p>"#.to_string() };
+    let bytes = to_bytes(&obj)?;
+    assert!(bytes.ends_with(br#"<doc><text>&#233;</text><fragment><p title="&#233;">&#233;<![CDATA[x]]></p></fragment></doc>"#));
+    Ok(())
+}
+
+#[test]
+fn serialize_unrepresentable_markup_fails() {
+    let in_cdata = AsciiDoc { text: "x".to_string(), fragment: cdata!("é") };
+    assert_eq!(to_bytes(&in_cdata).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+
+    let in_comment = AsciiDoc { text: "x".to_string(), fragment: "<!-- é -->".to_string() };
+    assert_eq!(to_bytes(&in_comment).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+
+    let in_name = AsciiDoc { text: "x".to_string(), fragment: "<é/>".to_string() };
+    assert_eq!(to_bytes(&in_name).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+
+    let in_element_name = AsciiNameDoc { text: "x".to_string() };
+    assert_eq!(to_bytes(&in_element_name).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,105 @@
+use xavier::{from_obj, from_obj_pretty, from_obj_with, from_xml, PError, SerializeOptions, XmlDeserializable, XmlSerializable};
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="item")]
+struct Item {
+    #[xml(attribute)]
+    pub id: u32,
+    pub label: String,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="note")]
+struct Note {
+    #[xml(attribute)]
+    pub lang: String,
+    #[xml(value)]
+    pub text: String,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[declaration(version="1.0", encoding="UTF-8", standalone="yes")]
+struct XMLObject {
+    pub title: String,
+    pub empty: String,
+    pub items: Vec<Item>,
+    #[xml(tree)]
+    pub note: Note,
+}
+
+#[derive(XmlSerializable)]
+#[xml(name="wide")]
+struct Wide {
+    #[xml(attribute)]
+    pub first_attribute: String,
+    #[xml(attribute)]
+    pub second_attribute: String,
+    #[xml(attribute)]
+    pub third_attribute: String,
+    pub child: i32,
+}
+
+fn sample() -> XMLObject {
+    XMLObject {
+        title: "Pretty".to_string(),
+        empty: "".to_string(),
+        items: vec![
+            Item { id: 1, label: "First".to_string() },
+            Item { id: 2, label: "Second".to_string() },
+        ],
+        note: Note { lang: "en".to_string(), text: "Some note".to_string() },
+    }
+}
+
+#[test]
+fn serialize_pretty() {
+    let should = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<XMLObject>
+  <title>Pretty</title>
+  <empty></empty>
+  <items>
+    <item id="1">
+      <label>First</label>
+    </item>
+    <item id="2">
+      <label>Second</label>
+    </item>
+  </items>
+  <note lang="en">Some note</note>
+</XMLObject>"#;
+    assert_eq!(from_obj_pretty(&sample()), should);
+}
+
+#[test]
+fn serialize_pretty_custom_indent_and_line_ending() {
+    let options = SerializeOptions::pretty().indent("\t").line_ending("\r\n");
+    let xml = from_obj_with(&Item { id: 3, label: "Tab".to_string() }, &options);
+    assert_eq!(xml, "<item id=\"3\">\r\n\t<label>Tab</label>\r\n</item>");
+}
+
+#[test]
+fn serialize_compact_options_match_from_obj() {
+    assert_eq!(from_obj_with(&sample(), &SerializeOptions::compact()), from_obj(&sample()));
+}
+
+#[test]
+fn serialize_pretty_wraps_long_attributes() {
+    let wide = Wide {
+        first_attribute: "a value".to_string(),
+        second_attribute: "another value".to_string(),
+        third_attribute: "yet another value".to_string(),
+        child: 1,
+    };
+    let should = "<wide first_attribute=\"a value\"\n  second_attribute=\"another value\"\n  third_attribute=\"yet another value\">\n  <child>1</child>\n</wide>";
+    assert_eq!(from_obj_with(&wide, &SerializeOptions::pretty().wrap_attributes(50)), should);
+
+    let unwrapped = from_obj_with(&wide, &SerializeOptions::pretty());
+    assert!(unwrapped.starts_with("<wide first_attribute=\"a value\" second_attribute=\"another value\" third_attribute"));
+}
+
+#[test]
+fn serialize_pretty_round_trip() -> Result<(), PError> {
+    let parsed: XMLObject = from_xml(&from_obj_pretty(&sample()))?;
+    assert_eq!(parsed, sample());
+    Ok(())
+}
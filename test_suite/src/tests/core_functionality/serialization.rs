@@ -3,6 +3,7 @@ use xavier::encode;
 
 #[derive(XmlSerializable)]
 struct XMLObject {
+    #[xml(raw)]
     pub some_string: String,
     pub some_int: i32,
     pub some_float: f32
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+use xavier::{from_obj, from_xml, PError, XmlDeserializable, XmlSerializable};
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+fn unknown() -> String {
+    "unknown".to_string()
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="user")]
+struct User {
+    #[xml(attribute, skip_serializing_if="is_zero")]
+    pub age: u32,
+    pub name: String,
+    #[xml(skip)]
+    pub cache: HashMap<String, String>,
+    #[xml(skip_deserializing, default="unknown")]
+    pub source: String,
+    #[xml(skip_serializing)]
+    pub password: Option<String>,
+    #[xml(inner="tag", skip_serializing_if="Vec::is_empty", default)]
+    pub tags: Vec<String>,
+    #[xml(skip_serializing_if="Option::is_none")]
+    pub email: Option<String>,
+}
+
+fn user() -> User {
+    User {
+        age: 0,
+        name: "ana".to_string(),
+        cache: HashMap::from([("k".to_string(), "v".to_string())]),
+        source: "db".to_string(),
+        password: Some("secret".to_string()),
+        tags: vec![],
+        email: None,
+    }
+}
+
+#[test]
+fn skipped_fields_are_not_written() {
+    assert_eq!(from_obj(&user()), "<user><name>ana</name><source>db</source></user>");
+
+    let user = User { age: 30, tags: vec!["a".to_string()], email: Some("a@b.c".to_string()), ..user() };
+    assert_eq!(from_obj(&user), r#"<user age="30"><name>ana</name><source>db</source><tags><tag>a</tag></tags><email>a@b.c</email></user>"#);
+}
+
+#[test]
+fn skipped_fields_are_not_read() -> Result<(), PError> {
+    let xml = r#"<user age="7"><name>ana</name><cache></cache><source>xml</source><password>p</password></user>"#;
+    let user: User = from_xml(xml)?;
+    assert_eq!(user.age, 7);
+    assert!(user.cache.is_empty());
+    assert_eq!(user.source, "unknown");
+    assert_eq!(user.password.as_deref(), Some("p"));
+    assert!(user.tags.is_empty());
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use std::io::{Cursor, Write};
+use xavier::{from_obj, to_writer, XmlSerializable};
+
+#[derive(XmlSerializable)]
+#[xml(name="item")]
+struct Item {
+    #[xml(attribute)]
+    pub id: u32,
+    pub label: String,
+}
+
+#[derive(XmlSerializable)]
+struct XMLObject {
+    #[xml(attribute, name="version")]
+    pub version: Option<String>,
+    pub title: String,
+    pub items: Vec<Item>,
+    #[xml(inner="tag")]
+    pub tags: Vec<String>,
+    pub note: Option<String>,
+}
+
+struct CountingWriter {
+    bytes: usize,
+    writes: usize,
+}
+
+impl Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.bytes += buf.len();
+        self.writes += 1;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn sample() -> XMLObject {
+    XMLObject {
+        version: Some("1.0".to_string()),
+        title: "Streaming".to_string(),
+        items: vec![
+            Item { id: 1, label: "First".to_string() },
+            Item { id: 2, label: "Second".to_string() },
+        ],
+        tags: vec!["a".to_string(), "b".to_string()],
+        note: None,
+    }
+}
+
+#[test]
+fn to_writer_matches_from_obj() -> std::io::Result<()> {
+    let obj = sample();
+    let mut buffer = Vec::new();
+    to_writer(&obj, &mut buffer)?;
+    assert_eq!(String::from_utf8(buffer).unwrap(), from_obj(&obj));
+    Ok(())
+}
+
+#[test]
+fn to_writer_output() -> std::io::Result<()> {
+    let should = r#"<XMLObject version="1.0"><title>Streaming</title><items><item id="1"><label>First</label></item><item id="2"><label>Second</label></item></items><tags><tag>a</tag><tag>b</tag></tags></XMLObject>"#;
+    let mut cursor = Cursor::new(Vec::new());
+    to_writer(&sample(), &mut cursor)?;
+    assert_eq!(String::from_utf8(cursor.into_inner()).unwrap(), should);
+    Ok(())
+}
+
+#[test]
+fn to_writer_buffers_the_sink() -> std::io::Result<()> {
+    let obj = sample();
+    let mut sink = CountingWriter { bytes: 0, writes: 0 };
+    to_writer(&obj, &mut sink)?;
+    assert_eq!(sink.bytes, from_obj(&obj).len());
+    assert_eq!(sink.writes, 1);
+    Ok(())
+}
+
+#[test]
+fn to_writer_escapes_attributes() -> std::io::Result<()> {
+    let obj = Item { id: 7, label: "x".to_string() };
+    let mut with_quotes = XMLObject { version: Some("\"1\" & <2>".to_string()), ..sample() };
+    with_quotes.items = vec![obj];
+    let mut buffer = Vec::new();
+    to_writer(&with_quotes, &mut buffer)?;
+    let xml = String::from_utf8(buffer).unwrap();
+    assert!(xml.starts_with(r#"<XMLObject version="&quot;1&quot; &amp; &lt;2&gt;">"#));
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use std::borrow::Cow;
+use xavier::{from_xml, PError, XmlDeserializable};
+
+#[derive(XmlDeserializable, Debug, PartialEq)]
+enum Level {
+    Low,
+    High,
+}
+
+#[derive(XmlDeserializable, Debug, PartialEq)]
+struct Code(u32);
+
+#[derive(XmlDeserializable, Debug, PartialEq)]
+#[xml(name="price")]
+struct Price {
+    #[xml(attribute)]
+    pub currency: String,
+    #[xml(value)]
+    pub amount: String,
+}
+
+#[derive(XmlDeserializable, Debug, PartialEq)]
+#[xml(name="entry")]
+struct Entry<'a> {
+    pub body: String,
+    pub count: u32,
+    pub level: Level,
+    pub code: Code,
+    pub label: Cow<'a, str>,
+    pub after: String,
+}
+
+#[test]
+fn text_fields_join_all_chunks() -> Result<(), PError> {
+    let xml = r#"<entry><body>foo<![CDATA[<bar>]]>baz &amp; <!--note-->qux<?skip?></body><count>1<!--x-->2</count><level>Hi<!---->gh</level><code><![CDATA[4]]>2</code><label>a<![CDATA[&]]>b</label><after>done</after></entry>"#;
+    let entry: Entry = from_xml(xml)?;
+    assert_eq!(entry.body, "foo<bar>baz & qux");
+    assert_eq!(entry.count, 12);
+    assert_eq!(entry.level, Level::High);
+    assert_eq!(entry.code, Code(42));
+    assert_eq!(entry.label, "a&b");
+    assert!(matches!(entry.label, Cow::Owned(_)));
+    // The rest of the text no longer leaks into the next field
+    assert_eq!(entry.after, "done");
+    Ok(())
+}
+
+#[test]
+fn value_fields_join_all_chunks() -> Result<(), PError> {
+    let price: Price = from_xml(r#"<price currency="EUR">1<!--thousand-->0<![CDATA[0]]>0 &lt; max</price>"#)?;
+    assert_eq!(price, Price { currency: "EUR".to_string(), amount: "1000 < max".to_string() });
+    Ok(())
+}
+
+#[test]
+fn nested_element_text_is_skipped() -> Result<(), PError> {
+    let xml = r#"<entry><body>x<b>y</b>z</body><count>1</count><level>Low</level><code>7</code><label>a<i><![CDATA[b]]></i>c</label><after>done</after></entry>"#;
+    let entry: Entry = from_xml(xml)?;
+    assert_eq!(entry.body, "xz");
+    assert_eq!(entry.label, "ac");
+    assert_eq!(entry.after, "done");
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use xavier::{from_xml, PError, XmlDeserializable};
+
+#[derive(XmlDeserializable, Debug, PartialEq)]
+enum Department {
+    #[xml(name="R&D")]
+    Research,
+    Sales,
+}
+
+#[derive(XmlDeserializable, Debug, PartialEq)]
+struct Motto(String);
+
+#[derive(XmlDeserializable, Debug, PartialEq)]
+#[xml(name="label")]
+struct Label {
+    #[xml(attribute)]
+    pub title: String,
+    #[xml(value)]
+    pub text: String,
+}
+
+#[derive(XmlDeserializable, Debug, PartialEq)]
+#[xml(name="team")]
+struct Team {
+    #[xml(attribute)]
+    pub name: String,
+    pub department: Department,
+    pub motto: Motto,
+    pub label: Label,
+    pub size: u8,
+}
+
+#[test]
+fn every_path_decodes_entities_alike() -> Result<(), PError> {
+    let xml = r#"<team name="Q&amp;A &lt;1&gt;"><department>R&amp;D</department><motto>Q&amp;A &lt;1&gt;</motto><label title="Q&amp;A &lt;1&gt;"><label>Q&amp;A &lt;1&gt;</label></label><size>12</size></team>"#;
+    let team: Team = from_xml(xml)?;
+    assert_eq!(team.name, "Q&A <1>");
+    assert_eq!(team.department, Department::Research);
+    assert_eq!(team.motto, Motto("Q&A <1>".to_string()));
+    assert_eq!(team.label, Label { title: "Q&A <1>".to_string(), text: "Q&A <1>".to_string() });
+    assert_eq!(team.size, 12);
+    Ok(())
+}
+
+#[test]
+fn every_path_rejects_control_characters() {
+    let cases = [
+        r#"<team name="a&#x01;"><department>Sales</department><motto>m</motto><label title="t"><label>x</label></label><size>1</size></team>"#,
+        r#"<team name="a"><department>Sales&#x01;</department><motto>m</motto><label title="t"><label>x</label></label><size>1</size></team>"#,
+        r#"<team name="a"><department>Sales</department><motto>m&#x01;</motto><label title="t"><label>x</label></label><size>1</size></team>"#,
+        r#"<team name="a"><department>Sales</department><motto>m</motto><label title="t"><label>x&#x01;</label></label><size>1</size></team>"#,
+    ];
+    for xml in cases {
+        let error = from_xml::<Team>(xml).unwrap_err();
+        assert!(error.to_string().contains("Malicious XML entities detected"), "{}", error);
+    }
+}
+
+#[test]
+fn character_references_are_decoded() -> Result<(), PError> {
+    let xml = r#"<team name="caf&#233; &#x20AC;"><department>R&#38;D</department><motto>&amp;lt;tag&amp;gt; &#x1F600;</motto><label title="&#x9;"><label>line&#10;break</label></label><size>&#x31;2</size></team>"#;
+    let team: Team = from_xml(xml)?;
+    assert_eq!(team.name, "café €");
+    assert_eq!(team.department, Department::Research);
+    assert_eq!(team.motto, Motto("&lt;tag&gt; 😀".to_string()));
+    assert_eq!(team.label, Label { title: "\t".to_string(), text: "line\nbreak".to_string() });
+    assert_eq!(team.size, 12);
+    Ok(())
+}
+
+#[test]
+fn invalid_references_are_errors() {
+    let cases = [
+        ("&#0;", "Malicious XML entities detected"),
+        ("&#xd800;", "Malicious XML entities detected"),
+        ("&#x110000;", "Malicious XML entities detected"),
+        ("&#xZZ;", "Invalid character reference '&#xZZ;'"),
+        ("&nbsp;", "Unknown entity '&nbsp;'"),
+        ("a &amp b", "Unterminated reference '&amp'"),
+    ];
+    for (motto, message) in cases {
+        let xml = format!(r#"<team name="a"><department>Sales</department><motto>{}</motto><label title="t"><label>x</label></label><size>1</size></team>"#, motto);
+        let error = from_xml::<Team>(&xml).unwrap_err();
+        assert!(error.to_string().contains(message), "{}", error);
+    }
+}
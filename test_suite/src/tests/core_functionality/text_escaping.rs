@@ -0,0 +1,95 @@
+use xavier::dom::Element;
+use xavier::{cdata, encode, from_obj, from_xml, PError, RawXml, XmlDeserializable, XmlSerializable};
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+struct XMLObject {
+    pub text: String,
+    pub optional: Option<String>,
+    #[xml(inner="item")]
+    pub items: Vec<String>,
+    pub symbol: char,
+}
+
+#[derive(XmlSerializable, Debug)]
+struct XMLObjectRaw {
+    #[xml(raw)]
+    pub fragment: String,
+    pub text: String,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+struct XMLObjectRawXml {
+    pub fragment: RawXml,
+}
+
+#[test]
+fn serialize_escapes_text() -> Result<(), PError> {
+    let obj = XMLObject {
+        text: "a < b && c > d".to_string(),
+        optional: Some("<tag>".to_string()),
+        items: vec!["x & y".to_string()],
+        symbol: '<',
+    };
+    let xml = from_obj(&obj);
+    assert!(xml.contains("<text>a &lt; b &amp;&amp; c &gt; d</text>"));
+    assert!(xml.contains("<optional>&lt;tag&gt;</optional>"));
+    assert!(xml.contains("<item>x &amp; y</item>"));
+    assert!(xml.contains("<symbol>&lt;</symbol>"));
+
+    let parsed: XMLObject = from_xml(&xml)?;
+    assert_eq!(parsed.text, obj.text);
+    assert_eq!(parsed.optional, obj.optional);
+    assert_eq!(parsed.items, obj.items);
+    Ok(())
+}
+
+#[test]
+fn serialize_round_trips_markup_like_text() -> Result<(), PError> {
+    let obj = XMLObject {
+        text: "&amp;".to_string(),
+        optional: Some("AT&T;".to_string()),
+        items: vec!["<!--x-->".to_string(), cdata!("<b>bold</b>"), encode!("Fish & Chips")],
+        symbol: '&',
+    };
+    let xml = from_obj(&obj);
+    assert!(xml.contains("<text>&amp;amp;</text>"));
+    assert!(xml.contains("<optional>AT&amp;T;</optional>"));
+    assert!(xml.contains("<item>&lt;!--x--&gt;</item>"));
+    assert!(xml.contains("<item>&lt;![CDATA[&lt;b&gt;bold&lt;/b&gt;]]&gt;</item>"));
+    assert_eq!(from_xml::<XMLObject>(&xml)?, obj);
+
+    let note = Element::new("note").with_text("AT&T; <!--x-->");
+    let parsed: Element = from_xml(&from_obj(&note))?;
+    assert_eq!(parsed, note);
+    Ok(())
+}
+
+#[test]
+fn serialize_encoded_text_through_raw() {
+    let obj = XMLObjectRaw { fragment: cdata!("<b>bold</b>"), text: encode!("Fish & Chips") };
+    let should = "<XMLObjectRaw><fragment><![CDATA[<b>bold</b>]]></fragment><text>Fish &amp;amp; Chips</text></XMLObjectRaw>";
+    assert_eq!(from_obj(&obj), should);
+}
+
+#[test]
+fn serialize_escapes_broken_references() {
+    let obj = XMLObject { text: "&nope &; &#xZZ;".to_string(), optional: None, items: vec![], symbol: 'x' };
+    assert!(from_obj(&obj).contains("<text>&amp;nope &amp;; &amp;#xZZ;</text>"));
+}
+
+#[test]
+fn serialize_raw_field() {
+    let obj = XMLObjectRaw { fragment: "<b>bold</b> & more".to_string(), text: "<b>".to_string() };
+    let should = "<XMLObjectRaw><fragment><b>bold</b> & more</fragment><text>&lt;b&gt;</text></XMLObjectRaw>";
+    assert_eq!(from_obj(&obj), should);
+}
+
+#[test]
+fn raw_xml_round_trip() -> Result<(), PError> {
+    let obj = XMLObjectRawXml { fragment: RawXml("<p>Hello <b>world</b> &amp; bye</p>".to_string()) };
+    let xml = from_obj(&obj);
+    assert_eq!(xml, "<XMLObjectRawXml><fragment><p>Hello <b>world</b> &amp; bye</p></fragment></XMLObjectRawXml>");
+    let parsed: XMLObjectRawXml = from_xml(&xml)?;
+    assert_eq!(parsed, obj);
+    Ok(())
+}
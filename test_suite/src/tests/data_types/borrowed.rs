@@ -0,0 +1,86 @@
+use std::borrow::Cow;
+use std::io::Cursor;
+use xavier::{from_obj, from_reader, from_slice, from_xml, PError, XmlDeserializable, XmlSerializable};
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="item")]
+struct Item<'a> {
+    pub label: &'a str,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="doc")]
+struct Doc<'a> {
+    #[xml(attribute)]
+    pub id: Cow<'a, str>,
+    pub name: &'a str,
+    pub title: Cow<'a, str>,
+    pub note: Option<Cow<'a, str>>,
+    pub items: Vec<Item<'a>>,
+}
+
+#[derive(XmlDeserializable, Debug)]
+#[xml(name="doc")]
+struct OwnedDoc {
+    pub name: Cow<'static, str>,
+    pub title: Cow<'static, str>,
+}
+
+fn points_into(text: &str, input: &str) -> bool {
+    let range = input.as_bytes().as_ptr_range();
+    range.contains(&text.as_ptr())
+}
+
+static XML: &str = r#"<doc id="d1"><name>plain</name><title>Tom &amp; Jerry</title><note><![CDATA[a < b]]></note><items><item><label>first</label></item><item><label>second</label></item></items></doc>"#;
+
+#[test]
+fn borrows_text_from_str_input() -> Result<(), PError> {
+    let doc: Doc = from_xml(XML)?;
+    assert_eq!(doc.name, "plain");
+    assert!(points_into(doc.name, XML));
+    assert_eq!(doc.items.iter().map(|item| item.label).collect::<Vec<_>>(), vec!["first", "second"]);
+    assert!(doc.items.iter().all(|item| points_into(item.label, XML)));
+
+    // Decoded text has to be owned, CDATA content is taken as is
+    assert_eq!(doc.title, "Tom & Jerry");
+    assert!(matches!(doc.title, Cow::Owned(_)));
+    assert_eq!(doc.note.as_deref(), Some("a < b"));
+    assert!(matches!(doc.note, Some(Cow::Borrowed(_))));
+    // Attributes come from the start event and are never borrowed (&'a str attributes don't compile)
+    assert_eq!(doc.id, "d1");
+    assert!(matches!(doc.id, Cow::Owned(_)));
+    Ok(())
+}
+
+#[test]
+fn borrows_text_from_utf8_slice() -> Result<(), PError> {
+    let input = format!("\u{FEFF}{}", XML);
+    let doc: Doc = from_slice(input.as_bytes())?;
+    assert!(points_into(doc.name, &input));
+    assert_eq!(from_obj(&doc), XML.replace("<![CDATA[a < b]]>", "a &lt; b"));
+    Ok(())
+}
+
+#[test]
+fn borrowed_str_needs_in_memory_input() {
+    // from_reader::<Doc, _> doesn't compile, a transcoded slice has nothing to borrow from either
+    let utf16: Vec<u8> = [0xFF, 0xFE].into_iter().chain(XML.encode_utf16().flat_map(|unit| unit.to_le_bytes())).collect();
+    let error = from_slice::<Doc>(&utf16).unwrap_err();
+    assert!(error.to_string().contains("can't be borrowed"));
+
+    let escaped = r#"<doc id="d1"><name>a &amp; b</name><title>t</title><items></items></doc>"#;
+    let error = from_xml::<Doc>(escaped).unwrap_err();
+    assert!(error.to_string().contains("Text 'a & b' can't be borrowed"));
+}
+
+#[test]
+fn cow_falls_back_to_owned_for_streams() -> Result<(), PError> {
+    let doc: OwnedDoc = from_reader(Cursor::new(b"<doc><name>n</name><title>streamed</title></doc>".to_vec()))?;
+    assert_eq!(doc.title, "streamed");
+    assert!(matches!(doc.title, Cow::Owned(_)));
+
+    // Cow<'static, str> never borrows, so it works with any input
+    let doc: OwnedDoc = from_xml("<doc><name>n</name><title>in memory</title></doc>")?;
+    assert_eq!(doc.name, "n");
+    Ok(())
+}
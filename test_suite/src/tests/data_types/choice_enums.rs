@@ -0,0 +1,80 @@
+use xavier::{from_obj, from_xml, PError, XmlDeserializable, XmlSerializable};
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="card")]
+struct Card {
+    #[xml(attribute)]
+    pub brand: String,
+    pub number: String,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(case="Camel")]
+enum Payment {
+    #[xml(tree)]
+    Card(Card),
+    Iban { #[xml(attribute)] country: String, number: String, bic: Option<String> },
+    #[xml(name="voucher")]
+    Coupon(String),
+    Cash,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="order")]
+struct Order {
+    pub id: u32,
+    pub payment: Payment,
+    pub fallback: Option<Payment>,
+}
+
+fn card() -> Payment {
+    Payment::Card(Card { brand: "visa".to_string(), number: "4111".to_string() })
+}
+
+fn iban() -> Payment {
+    Payment::Iban { country: "DE".to_string(), number: "DE89".to_string(), bic: None }
+}
+
+#[test]
+fn serialize_choice_variants() {
+    let order = Order { id: 1, payment: card(), fallback: Some(iban()) };
+    let should = r#"<order><id>1</id><payment><card brand="visa"><number>4111</number></card></payment><fallback><iban country="DE"><number>DE89</number></iban></fallback></order>"#;
+    assert_eq!(from_obj(&order), should);
+
+    let order = Order { id: 2, payment: Payment::Coupon("X&Y".to_string()), fallback: Some(Payment::Cash) };
+    let should = r#"<order><id>2</id><payment><voucher>X&amp;Y</voucher></payment><fallback><cash></cash></fallback></order>"#;
+    assert_eq!(from_obj(&order), should);
+}
+
+#[test]
+fn deserialize_choice_variants() -> Result<(), PError> {
+    let xml = r#"
+    <order>
+        <id>1</id>
+        <payment><card brand="visa"><number>4111</number></card></payment>
+        <fallback><iban country="DE"><number>DE89</number></iban></fallback>
+    </order>"#;
+    let order: Order = from_xml(xml)?;
+    assert_eq!(order, Order { id: 1, payment: card(), fallback: Some(iban()) });
+
+    let xml = r#"<order><id>2</id><payment><voucher>X&amp;Y</voucher></payment><fallback><cash/></fallback></order>"#;
+    let order: Order = from_xml(xml)?;
+    assert_eq!(order, Order { id: 2, payment: Payment::Coupon("X&Y".to_string()), fallback: Some(Payment::Cash) });
+    Ok(())
+}
+
+#[test]
+fn choice_round_trip_as_root() -> Result<(), PError> {
+    for payment in [card(), iban(), Payment::Coupon("abc".to_string()), Payment::Cash] {
+        let parsed: Payment = from_xml(&from_obj(&payment))?;
+        assert_eq!(parsed, payment);
+    }
+    Ok(())
+}
+
+#[test]
+fn deserialize_choice_unexpected_element() {
+    let xml = r#"<order><id>1</id><payment><cheque>12</cheque></payment></order>"#;
+    let error = from_xml::<Order>(xml).unwrap_err();
+    assert!(error.to_string().contains("Unexpected element 'cheque' for Payment, expected one of: card, iban, voucher, cash"));
+}
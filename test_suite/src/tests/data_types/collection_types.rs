@@ -0,0 +1,160 @@
+use std::collections::{BTreeSet, HashSet, LinkedList, VecDeque};
+use xavier::{from_obj, from_xml, PError, XmlDeserializable, XmlSerializable};
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone)]
+#[xml(name="point")]
+struct Point {
+    #[xml(attribute)]
+    pub x: i32,
+    #[xml(attribute)]
+    pub y: i32,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="shape")]
+struct Shape {
+    pub corners: [Point; 2],
+    pub path: VecDeque<Point>,
+    #[xml(flatten)]
+    pub marks: BTreeSet<Point>,
+    #[xml(inner="id")]
+    pub ids: BTreeSet<u32>,
+    #[xml(inner="label")]
+    pub labels: Box<[String]>,
+    #[xml(inner="step")]
+    pub steps: LinkedList<i64>,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="group")]
+struct Group {
+    pub members: HashSet<Point>,
+    #[xml(inner="weight")]
+    pub weights: Option<[u8; 3]>,
+}
+
+/* A collection the library knows nothing about, only FromIterator and IntoIterator */
+#[derive(Debug, PartialEq)]
+struct Tags(Vec<String>);
+
+impl FromIterator<String> for Tags {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        Tags(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for Tags {
+    type Item = String;
+    type IntoIter = std::vec::IntoIter<String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Tags {
+    type Item = &'a String;
+    type IntoIter = std::slice::Iter<'a, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="post")]
+struct Post {
+    #[xml(collection, inner="tag")]
+    pub tags: Tags,
+    #[xml(collection, flatten)]
+    pub points: Trail,
+}
+
+#[derive(Debug, PartialEq)]
+struct Trail(Vec<Point>);
+
+impl FromIterator<Point> for Trail {
+    fn from_iter<I: IntoIterator<Item = Point>>(iter: I) -> Self {
+        Trail(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for Trail {
+    type Item = Point;
+    type IntoIter = std::vec::IntoIter<Point>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Trail {
+    type Item = &'a Point;
+    type IntoIter = std::slice::Iter<'a, Point>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+fn point(x: i32, y: i32) -> Point {
+    Point { x, y }
+}
+
+#[test]
+fn std_collections_round_trip() -> Result<(), PError> {
+    let shape = Shape {
+        corners: [point(0, 0), point(4, 3)],
+        path: VecDeque::from([point(1, 1), point(2, 2)]),
+        marks: BTreeSet::from([point(9, 9), point(5, 5)]),
+        ids: BTreeSet::from([3, 1, 2]),
+        labels: vec!["a".to_string(), "b".to_string()].into_boxed_slice(),
+        steps: LinkedList::from([10, -20]),
+    };
+    let xml = from_obj(&shape);
+    assert_eq!(xml, concat!(
+        r#"<shape><corners><point x="0" y="0"></point><point x="4" y="3"></point></corners>"#,
+        r#"<path><point x="1" y="1"></point><point x="2" y="2"></point></path>"#,
+        r#"<point x="5" y="5"></point><point x="9" y="9"></point>"#,
+        r#"<ids><id>1</id><id>2</id><id>3</id></ids>"#,
+        r#"<labels><label>a</label><label>b</label></labels>"#,
+        r#"<steps><step>10</step><step>-20</step></steps></shape>"#));
+    assert_eq!(from_xml::<Shape>(&xml)?, shape);
+    Ok(())
+}
+
+#[test]
+fn sets_and_optional_arrays() -> Result<(), PError> {
+    let xml = r#"
+    <group>
+        <members><point x="1" y="2"/><point x="1" y="2"/><point x="3" y="4"/></members>
+        <weights><weight>7</weight><weight>8</weight><weight>9</weight></weights>
+    </group>"#;
+    let group: Group = from_xml(xml)?;
+    assert_eq!(group.members, HashSet::from([point(1, 2), point(3, 4)]));
+    assert_eq!(group.weights, Some([7, 8, 9]));
+
+    let group = Group { members: HashSet::from([point(0, 1)]), weights: None };
+    let xml = from_obj(&group);
+    assert_eq!(xml, r#"<group><members><point x="0" y="1"></point></members><weights></weights></group>"#);
+    Ok(())
+}
+
+#[test]
+fn array_length_mismatch_is_an_error() {
+    let xml = r#"<group><members></members><weights><weight>1</weight></weights></group>"#;
+    let error = from_xml::<Group>(xml).unwrap_err();
+    assert!(error.to_string().contains("Expected 3 items but found 1"));
+}
+
+#[test]
+fn custom_collection_with_attribute() -> Result<(), PError> {
+    let post = Post {
+        tags: Tags(vec!["rust".to_string(), "xml".to_string()]),
+        points: Trail(vec![point(1, 2), point(3, 4)]),
+    };
+    let xml = from_obj(&post);
+    assert_eq!(xml, r#"<post><tags><tag>rust</tag><tag>xml</tag></tags><point x="1" y="2"></point><point x="3" y="4"></point></post>"#);
+    assert_eq!(from_xml::<Post>(&xml)?, post);
+    Ok(())
+}
@@ -255,4 +255,47 @@ fn sibling_collections() -> Result<(), PError> {
     assert!(result.contains("Sibling B1"));
 
     Ok(())
-} 
\ No newline at end of file
+} 
+#[test]
+fn recursive_and_flat_children() -> Result<(), PError> {
+    let test_data = XMLObjectRecursion {
+        field_a: "Recursion".to_string(),
+        child: ChildRecursive {
+            attribute: "outer".to_string(),
+            child_field_a: "Value A".to_string(),
+            inner: Some(Box::new(ChildRecursive { attribute: "inner".to_string(), child_field_a: "Value B".to_string(), inner: None })),
+        },
+    };
+    let parsed: XMLObjectRecursion = from_xml(&from_obj(&test_data))?;
+    assert_eq!(parsed.field_a, "Recursion");
+    assert_eq!(parsed.child.attribute, "outer");
+    let inner = parsed.child.inner.expect("inner child");
+    assert_eq!((inner.attribute.as_str(), inner.child_field_a.as_str()), ("inner", "Value B"));
+    assert!(inner.inner.is_none());
+
+    let test_data = XMLObjectFlat {
+        field_a: "Flat".to_string(),
+        child_a: ChildFlat { attribute: "a".to_string(), child_field_a: "Value A".to_string() },
+        child_b: ChildFlat { attribute: "b".to_string(), child_field_a: "Value B".to_string() },
+    };
+    let parsed: XMLObjectFlat = from_xml(&from_obj(&test_data))?;
+    assert_eq!((parsed.child_a.attribute.as_str(), parsed.child_a.child_field_a.as_str()), ("a", "Value A"));
+    assert_eq!((parsed.child_b.attribute.as_str(), parsed.child_b.child_field_a.as_str()), ("b", "Value B"));
+    Ok(())
+}
+
+#[test]
+fn single_sibling_collection() -> Result<(), PError> {
+    let test_data = XMLObjectSibling {
+        field_a: "Sibling".to_string(),
+        siblings: vec![
+            ChildSibling { child_field_a: "Sibling 1".to_string() },
+            ChildSibling { child_field_a: "Sibling 2".to_string() },
+        ],
+    };
+    let parsed: XMLObjectSibling = from_xml(&from_obj(&test_data))?;
+    assert_eq!(parsed.field_a, "Sibling");
+    let values: Vec<_> = parsed.siblings.iter().map(|sibling| sibling.child_field_a.as_str()).collect();
+    assert_eq!(values, ["Sibling 1", "Sibling 2"]);
+    Ok(())
+}
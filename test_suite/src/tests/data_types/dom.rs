@@ -0,0 +1,96 @@
+use xavier::dom::{Element, XmlNode};
+use xavier::{cdata, from_dom, from_obj, from_xml, to_dom, PError, XmlDeserializable, XmlSerializable};
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="book")]
+struct Book {
+    #[xml(attribute)]
+    pub isbn: String,
+    pub title: String,
+    pub pages: u32,
+}
+
+#[derive(XmlSerializable)]
+#[xml(name="page")]
+struct Page {
+    #[xml(attribute)]
+    pub lang: String,
+    pub title: String,
+    #[xml(raw)]
+    pub body: String,
+    pub note: String,
+}
+
+#[test]
+fn parse_and_navigate() -> Result<(), PError> {
+    let xml = r#"<library city="Lisbon"><?sort by="title"?><book isbn="1"><title>Dune</title></book><!--shelf 2--><book isbn="2"><title><![CDATA[Emma & co]]></title></book><note>open &amp; free</note></library>"#;
+    let library: Element = from_xml(xml)?;
+    assert_eq!(library.name, "library");
+    assert_eq!(library.attribute("city"), Some("Lisbon"));
+    assert_eq!(library.attribute("country"), None);
+    assert_eq!(library.children[0], XmlNode::PI(r#"sort by="title""#.to_string()));
+    assert_eq!(library.children[2], XmlNode::Comment("shelf 2".to_string()));
+
+    let isbns: Vec<_> = library.children_named("book").filter_map(|book| book.attribute("isbn")).collect();
+    assert_eq!(isbns, vec!["1", "2"]);
+    assert_eq!(library.child("book").and_then(|book| book.child("title")).map(Element::text), Some("Dune".to_string()));
+    assert_eq!(library.children_named("book").nth(1).unwrap().child("title").unwrap().text(), "Emma & co");
+    assert_eq!(library.child("note").unwrap().text(), "open & free");
+
+    // Everything is written back as it was read
+    assert_eq!(from_obj(&library), xml);
+    Ok(())
+}
+
+#[test]
+fn build_and_mutate() {
+    let mut list = Element::new("list")
+        .with_attribute("kind", "todo")
+        .with_child(Element::new("item").with_text("a < b"))
+        .with_child(XmlNode::Comment("later".to_string()));
+    list.push(Element::new("item").with_attribute("done", "true"));
+    list.insert(0, Element::new("title").with_text("Todo"));
+    list.set_attribute("kind", "tasks");
+    list.set_attribute("owner", "ana");
+    assert_eq!(from_obj(&list), r#"<list kind="tasks" owner="ana"><title>Todo</title><item>a &lt; b</item><!--later--><item done="true"/></list>"#);
+
+    assert_eq!(list.remove_attribute("owner"), Some("ana".to_string()));
+    assert_eq!(list.remove_child("title").map(|title| title.text()), Some("Todo".to_string()));
+    assert_eq!(list.remove(1), Some(XmlNode::Comment("later".to_string())));
+    assert_eq!(list.remove(5), None);
+    list.child_mut("item").unwrap().set_text("done");
+    for item in list.elements_mut() {
+        item.set_attribute("seen", "1");
+    }
+    assert_eq!(from_obj(&list), r#"<list kind="tasks"><item seen="1">done</item><item done="true" seen="1"/></list>"#);
+}
+
+#[test]
+fn convert_derived_types() -> Result<(), PError> {
+    let book = Book { isbn: "42".to_string(), title: "Dune".to_string(), pages: 412 };
+    let mut element = to_dom(&book)?;
+    assert_eq!(element.name, "book");
+    assert_eq!(element.attribute("isbn"), Some("42"));
+    assert_eq!(element.child("pages").unwrap().text(), "412");
+
+    element.child_mut("title").unwrap().set_text("Dune Messiah");
+    element.set_attribute("isbn", "43");
+    let changed: Book = from_dom(&element)?;
+    assert_eq!(changed, Book { isbn: "43".to_string(), title: "Dune Messiah".to_string(), pages: 412 });
+    Ok(())
+}
+
+#[test]
+fn to_dom_builds_what_would_be_parsed() -> Result<(), PError> {
+    let page = Page {
+        lang: "en & pt".to_string(),
+        title: " a < b ".to_string(),
+        body: r#"<p class="x">one <b>two</b> <i>three</i></p><!--end-->"#.to_string(),
+        note: cdata!("1 < 2"),
+    };
+    let element = to_dom(&page)?;
+    assert_eq!(element, from_xml::<Element>(&from_obj(&page))?);
+    assert_eq!(element.child("body").unwrap().child("p").unwrap().children[2], XmlNode::Text(" ".to_string()));
+    assert_eq!(from_obj(&element), from_obj(&page));
+    Ok(())
+}
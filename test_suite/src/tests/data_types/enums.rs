@@ -1,6 +1,7 @@
+use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::str::FromStr;
-use xavier::{from_xml, from_obj, XmlSerializable, XmlDeserializable, PError};
+use xavier::{from_xml, from_obj, XmlSerializable, XmlDeserializable, PError, ToXmlText, FromXmlText};
 
 #[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
 enum SimpleEnum {
@@ -132,3 +133,102 @@ fn round_trip_enum_variant_c() -> Result<(), PError> {
     assert_eq!(original, parsed);
     Ok(())
 }
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(case="Kebab")]
+enum NamedEnum {
+    First,
+    #[xml(name="other")]
+    Second,
+    #[xml(name="LAST", ignore_case="true")]
+    Last,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+struct NamedObject {
+    pub choice: NamedEnum,
+}
+
+#[test]
+fn serialize_enum_with_names_and_case() {
+    assert_eq!(from_obj(&NamedObject { choice: NamedEnum::First }), "<NamedObject><choice>first</choice></NamedObject>");
+    assert_eq!(from_obj(&NamedObject { choice: NamedEnum::Second }), "<NamedObject><choice>other</choice></NamedObject>");
+    assert_eq!(from_obj(&NamedObject { choice: NamedEnum::Last }), "<NamedObject><choice>LAST</choice></NamedObject>");
+}
+
+#[test]
+fn deserialize_enum_with_names_and_case() -> Result<(), PError> {
+    let obj: NamedObject = from_xml("<NamedObject><choice> first </choice></NamedObject>")?;
+    assert_eq!(obj.choice, NamedEnum::First);
+    let obj: NamedObject = from_xml("<NamedObject><choice>LAST</choice></NamedObject>")?;
+    assert_eq!(obj.choice, NamedEnum::Last);
+    Ok(())
+}
+
+#[test]
+fn deserialize_enum_invalid_value() {
+    let error = from_xml::<NamedObject>("<NamedObject><choice>SecondChoice</choice></NamedObject>").unwrap_err();
+    assert!(error.to_string().contains("Invalid value 'SecondChoice' for NamedEnum, expected one of: first, other, LAST"));
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[xml(case="Kebab")]
+enum Priority {
+    Low,
+    #[xml(alias="urgent")]
+    VeryHigh,
+}
+
+// The XML text comes from the names, Display is left to the type
+impl Display for Priority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} priority", self)
+    }
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+struct Task {
+    #[xml(attribute)]
+    pub priority: Priority,
+    #[xml(attribute)]
+    pub fallback: Option<Priority>,
+    #[xml(value)]
+    pub level: Priority,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+struct Board {
+    pub counts: BTreeMap<Priority, u32>,
+}
+
+#[test]
+fn enum_as_attribute_and_value() -> Result<(), PError> {
+    let task = Task { priority: Priority::VeryHigh, fallback: Some(Priority::Low), level: Priority::Low };
+    let xml = from_obj(&task);
+    assert_eq!(xml, r#"<Task priority="very-high" fallback="low">low</Task>"#);
+    assert_eq!(from_xml::<Task>(&xml)?, task);
+
+    let parsed: Task = from_xml(r#"<Task priority="urgent">very-high</Task>"#)?;
+    assert_eq!(parsed, Task { priority: Priority::VeryHigh, fallback: None, level: Priority::VeryHigh });
+
+    let error = from_xml::<Task>(r#"<Task priority="high">low</Task>"#).unwrap_err();
+    assert!(error.to_string().contains("Invalid value 'high' for Priority, expected one of: low, very-high"));
+    Ok(())
+}
+
+#[test]
+fn enum_as_map_key() -> Result<(), PError> {
+    let board = Board { counts: BTreeMap::from([(Priority::Low, 3), (Priority::VeryHigh, 1)]) };
+    let xml = from_obj(&board);
+    assert_eq!(xml, "<Board><counts><low>3</low><very-high>1</very-high></counts></Board>");
+    assert_eq!(from_xml::<Board>(&xml)?, board);
+    Ok(())
+}
+
+#[test]
+fn enum_text_conversion() -> Result<(), PError> {
+    assert_eq!(Priority::VeryHigh.to_xml_text(), "very-high");
+    assert_eq!(Priority::from_xml_text("urgent")?, Priority::VeryHigh);
+    assert_eq!(Priority::VeryHigh.to_string(), "VeryHigh priority");
+    Ok(())
+}
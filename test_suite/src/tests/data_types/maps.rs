@@ -0,0 +1,220 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use xavier::{from_obj, from_obj_with, from_xml, to_bytes_with, PError, SerializeOptions, ToXmlText, XmlDeserializable, XmlSerializable};
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="settings")]
+struct Settings {
+    pub values: HashMap<String, String>,
+    pub limits: BTreeMap<String, u32>,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="scores")]
+struct Scores {
+    pub points: HashMap<String, i64>,
+    pub labels: Option<HashMap<String, String>>,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="inventory")]
+struct Inventory {
+    #[xml(entry)]
+    pub stock: HashMap<u32, String>,
+    #[xml(entry="item", key="sku")]
+    pub prices: BTreeMap<String, f64>,
+    #[xml(entry)]
+    pub extra: Option<BTreeMap<u16, bool>>,
+}
+
+#[test]
+fn deserialize_element_name_keys() -> Result<(), PError> {
+    let xml = r#"
+    <settings>
+        <values><theme>dark</theme><lang>en &amp; fr</lang></values>
+        <limits><upload>10</upload><download>20</download></limits>
+    </settings>"#;
+    let settings: Settings = from_xml(xml)?;
+    assert_eq!(settings.values.len(), 2);
+    assert_eq!(settings.values["theme"], "dark");
+    assert_eq!(settings.values["lang"], "en & fr");
+    assert_eq!(settings.limits, BTreeMap::from([("download".to_string(), 20), ("upload".to_string(), 10)]));
+    Ok(())
+}
+
+#[test]
+fn element_name_round_trip() -> Result<(), PError> {
+    let scores = Scores { points: HashMap::from([("alice".to_string(), -3)]), labels: None };
+    let xml = from_obj(&scores);
+    assert_eq!(xml, "<scores><points><alice>-3</alice></points></scores>");
+    assert_eq!(from_xml::<Scores>(&xml)?, scores);
+    Ok(())
+}
+
+#[test]
+fn deserialize_entry_layout() -> Result<(), PError> {
+    let xml = r#"
+    <inventory>
+        <stock><entry key="1">bolt</entry><entry key="2">nut</entry></stock>
+        <prices><item sku="a&amp;b">1.5</item><item sku="c">2</item></prices>
+    </inventory>"#;
+    let inventory: Inventory = from_xml(xml)?;
+    assert_eq!(inventory.stock, HashMap::from([(1, "bolt".to_string()), (2, "nut".to_string())]));
+    assert_eq!(inventory.prices, BTreeMap::from([("a&b".to_string(), 1.5), ("c".to_string(), 2.0)]));
+    assert_eq!(inventory.extra, None);
+    Ok(())
+}
+
+#[test]
+fn entry_layout_round_trip() -> Result<(), PError> {
+    let inventory = Inventory {
+        stock: HashMap::from([(7, "washer".to_string())]),
+        prices: BTreeMap::from([("x".to_string(), 0.5), ("y".to_string(), 3.0)]),
+        extra: Some(BTreeMap::from([(1, true), (2, false)])),
+    };
+    let xml = from_obj(&inventory);
+    assert_eq!(xml, concat!(
+        r#"<inventory><stock><entry key="7">washer</entry></stock>"#,
+        r#"<prices><item sku="x">0.5</item><item sku="y">3</item></prices>"#,
+        r#"<extra><entry key="1">true</entry><entry key="2">false</entry></extra></inventory>"#));
+    assert_eq!(from_xml::<Inventory>(&xml)?, inventory);
+    Ok(())
+}
+
+#[test]
+fn entry_layout_errors() {
+    let missing_key = r#"<inventory><stock><entry>bolt</entry></stock><prices></prices></inventory>"#;
+    let error = from_xml::<Inventory>(missing_key).unwrap_err();
+    assert!(error.to_string().contains("Attribute 'key' not found for map entry"));
+
+    let bad_key = r#"<inventory><stock><entry key="one">bolt</entry></stock><prices></prices></inventory>"#;
+    assert!(from_xml::<Inventory>(bad_key).is_err());
+
+    let wrong_entry = r#"<inventory><stock><value key="1">bolt</value></stock><prices></prices></inventory>"#;
+    let error = from_xml::<Inventory>(wrong_entry).unwrap_err();
+    assert!(error.to_string().contains("Unexpected element 'value' in map, expected 'entry'"));
+}
+
+#[derive(XmlSerializable, Debug, PartialEq)]
+#[xml(name="node")]
+struct Node {
+    #[xml(attribute)]
+    pub zeta: u8,
+    #[xml(attribute)]
+    pub alpha: u8,
+    #[xml(entry)]
+    pub tags: HashMap<String, u8>,
+}
+
+#[test]
+fn btree_map_serializes_in_key_order() -> Result<(), PError> {
+    let settings = Settings {
+        values: HashMap::new(),
+        limits: BTreeMap::from([("upload".to_string(), 10), ("download".to_string(), 20)]),
+    };
+    let xml = from_obj(&settings);
+    assert_eq!(xml, "<settings><values></values><limits><download>20</download><upload>10</upload></limits></settings>");
+    assert_eq!(from_xml::<Settings>(&xml)?, settings);
+    Ok(())
+}
+
+#[test]
+fn deterministic_output_is_byte_identical() {
+    let options = SerializeOptions::compact().deterministic();
+    let values: HashMap<String, String> = (0..32).map(|index| (format!("key{:02}", index), index.to_string())).collect();
+    let settings = Settings { values: values.clone(), limits: BTreeMap::new() };
+
+    let first = from_obj_with(&settings, &options);
+    for _ in 0..8 {
+        // Fresh maps get a new hash seed, the output must not depend on it
+        let settings = Settings { values: values.clone().into_iter().collect(), limits: BTreeMap::new() };
+        assert_eq!(from_obj_with(&settings, &options), first);
+        assert_eq!(to_bytes_with(&settings, &options).unwrap(), first.as_bytes());
+    }
+    assert!(first.starts_with("<settings><values><key00>0</key00><key01>1</key01><key02>2</key02>"));
+}
+
+#[test]
+fn deterministic_sorts_entries_and_attributes() {
+    let node = Node { zeta: 1, alpha: 2, tags: HashMap::from([("b".to_string(), 2), ("a".to_string(), 1), ("c".to_string(), 3)]) };
+    let should = r#"<node alpha="2" zeta="1"><tags><entry key="a">1</entry><entry key="b">2</entry><entry key="c">3</entry></tags></node>"#;
+    assert_eq!(from_obj_with(&node, &SerializeOptions::compact().deterministic()), should);
+
+    let pretty = from_obj_with(&node, &SerializeOptions::pretty().deterministic());
+    assert!(pretty.starts_with("<node alpha=\"2\" zeta=\"1\">\n  <tags>\n    <entry key=\"a\">1</entry>"));
+
+    // Without the option attributes keep the declaration order
+    assert!(from_obj(&node).starts_with(r#"<node zeta="1" alpha="2">"#));
+}
+
+#[derive(XmlSerializable, Debug, PartialEq)]
+#[xml(name="batch")]
+struct Batch {
+    #[xml(entry)]
+    pub ordered: BTreeMap<u32, u8>,
+    #[xml(entry)]
+    pub unordered: HashMap<u32, u8>,
+    #[xml(inner="n")]
+    pub ids: HashSet<u32>,
+    pub flags: HashSet<u32>,
+}
+
+#[test]
+fn deterministic_orders_by_written_key_and_item() {
+    let batch = Batch {
+        ordered: BTreeMap::from([(10, 1), (9, 2)]),
+        unordered: HashMap::from([(10, 1), (9, 2), (100, 3)]),
+        ids: HashSet::from([11, 6, 10, 8, 2, 100]),
+        flags: HashSet::from([3, 20, 1]),
+    };
+    // Keys sort by their text and items by their serialized bytes, flags has no inner element
+    let should = concat!(
+        r#"<batch><ordered><entry key="9">2</entry><entry key="10">1</entry></ordered>"#,
+        r#"<unordered><entry key="10">1</entry><entry key="100">3</entry><entry key="9">2</entry></unordered>"#,
+        "<ids><n>100</n><n>10</n><n>11</n><n>2</n><n>6</n><n>8</n></ids><flags>1203</flags></batch>",
+    );
+    assert_eq!(from_obj_with(&batch, &SerializeOptions::compact().deterministic()), should);
+
+    // Ordered maps keep their own order either way
+    assert!(from_obj(&batch).starts_with(r#"<batch><ordered><entry key="9">2</entry><entry key="10">1</entry></ordered>"#));
+}
+
+#[derive(XmlSerializable, Debug, PartialEq, Eq, Hash)]
+#[xml(name="point")]
+struct Point {
+    #[xml(attribute)]
+    pub x: i32,
+    #[xml(attribute)]
+    pub y: i32,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct Code(u8);
+
+impl ToXmlText for Code {
+    fn to_xml_text(&self) -> Cow<'_, str> {
+        Cow::Owned(format!("c{}", self.0))
+    }
+}
+
+#[derive(XmlSerializable)]
+#[xml(name="plot")]
+struct Plot {
+    pub points: HashSet<Point>,
+    #[xml(entry)]
+    pub codes: HashMap<Code, u8>,
+}
+
+#[test]
+fn deterministic_without_ord() {
+    let plot = Plot {
+        points: HashSet::from([Point { x: 2, y: 0 }, Point { x: 1, y: 5 }, Point { x: 1, y: 3 }]),
+        codes: HashMap::from([(Code(2), 1), (Code(1), 2)]),
+    };
+    let should = concat!(
+        r#"<plot><points><point x="1" y="3"></point><point x="1" y="5"></point><point x="2" y="0"></point></points>"#,
+        r#"<codes><entry key="c1">2</entry><entry key="c2">1</entry></codes></plot>"#,
+    );
+    assert_eq!(from_obj_with(&plot, &SerializeOptions::compact().deterministic()), should);
+    assert_eq!(from_obj(&plot).len(), should.len());
+}
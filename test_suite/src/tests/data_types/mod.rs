@@ -3,3 +3,10 @@ pub mod structs;
 pub mod options;
 pub mod enums;
 pub mod primitive;
+pub mod choice_enums;
+pub mod tagged_enums;
+pub mod maps;
+pub mod collection_types;
+pub mod pointers;
+pub mod borrowed;
+pub mod dom;
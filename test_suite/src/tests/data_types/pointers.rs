@@ -0,0 +1,120 @@
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::panic;
+use xavier::{from_obj, from_xml, to_string, PError, XmlDeserializable, XmlSerializable};
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="limits")]
+struct Limits {
+    pub max: u32,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug)]
+#[xml(name="config")]
+struct Config<'a> {
+    #[xml(attribute)]
+    pub id: Arc<str>,
+    #[xml(attribute)]
+    pub owner: Option<Cow<'a, str>>,
+    pub title: Cow<'a, str>,
+    #[xml(tree)]
+    pub limits: Arc<Limits>,
+    #[xml(tree)]
+    pub fallback: Option<Arc<Limits>>,
+    pub boxed: Box<Limits>,
+    pub shared: Rc<String>,
+    pub hits: Mutex<u32>,
+    pub cache: RwLock<Vec<Limits>>,
+    pub cell: RefCell<i64>,
+    pub counter: Option<Arc<Mutex<u8>>>,
+}
+
+#[test]
+fn pointer_fields_round_trip() -> Result<(), PError> {
+    let config = Config {
+        id: Arc::from("main"),
+        owner: Some(Cow::Borrowed("ops & dev")),
+        title: Cow::Borrowed("Main <config>"),
+        limits: Arc::new(Limits { max: 10 }),
+        fallback: None,
+        boxed: Box::new(Limits { max: 1 }),
+        shared: Rc::new("shared".to_string()),
+        hits: Mutex::new(3),
+        cache: RwLock::new(vec![Limits { max: 5 }]),
+        cell: RefCell::new(-1),
+        counter: Some(Arc::new(Mutex::new(7))),
+    };
+    let xml = from_obj(&config);
+    assert_eq!(xml, concat!(
+        r#"<config id="main" owner="ops &amp; dev"><title>Main &lt;config&gt;</title>"#,
+        r#"<limits><max>10</max></limits><boxed><limits><max>1</max></limits></boxed>"#,
+        r#"<shared>shared</shared><hits>3</hits><cache><limits><max>5</max></limits></cache>"#,
+        r#"<cell>-1</cell><counter>7</counter></config>"#));
+
+    let parsed: Config = from_xml(&xml)?;
+    assert_eq!(&*parsed.id, "main");
+    assert_eq!(parsed.owner.as_deref(), Some("ops & dev"));
+    assert_eq!(parsed.title, "Main <config>");
+    assert_eq!(*parsed.limits, Limits { max: 10 });
+    assert!(parsed.fallback.is_none());
+    assert_eq!(*parsed.boxed, Limits { max: 1 });
+    assert_eq!(*parsed.shared, "shared");
+    assert_eq!(*parsed.hits.lock().unwrap(), 3);
+    assert_eq!(*parsed.cache.read().unwrap(), vec![Limits { max: 5 }]);
+    assert_eq!(*parsed.cell.borrow(), -1);
+    assert_eq!(*parsed.counter.unwrap().lock().unwrap(), 7);
+    Ok(())
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="tree")]
+struct Tree {
+    #[xml(attribute)]
+    pub name: String,
+    #[xml(flatten)]
+    pub children: Option<Vec<Arc<Tree>>>,
+}
+
+#[test]
+fn shared_tree_across_threads() -> Result<(), PError> {
+    let xml = r#"<tree name="root"><tree name="a"></tree><tree name="b"><tree name="c"></tree></tree></tree>"#;
+    let tree: Arc<Tree> = Arc::new(from_xml(xml)?);
+
+    let handles: Vec<_> = (0..4).map(|_| {
+        let tree = Arc::clone(&tree);
+        thread::spawn(move || from_obj(&tree))
+    }).collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), xml);
+    }
+    assert_eq!(tree.children.as_ref().unwrap()[1].children.as_ref().unwrap()[0].name, "c");
+    Ok(())
+}
+
+#[derive(XmlSerializable)]
+#[xml(name="counters")]
+struct Counters {
+    pub cell: RefCell<i64>,
+    pub hits: Arc<Mutex<u32>>,
+}
+
+#[test]
+fn borrowed_or_poisoned_values_are_errors() {
+    let counters = Counters { cell: RefCell::new(1), hits: Arc::new(Mutex::new(2)) };
+    assert_eq!(to_string(&counters).unwrap(), "<counters><cell>1</cell><hits>2</hits></counters>");
+
+    let borrowed = counters.cell.borrow_mut();
+    assert_eq!(to_string(&counters).unwrap_err().to_string(), "RefCell is mutably borrowed");
+    assert!(panic::catch_unwind(panic::AssertUnwindSafe(|| from_obj(&counters))).is_err());
+    drop(borrowed);
+
+    let hits = Arc::clone(&counters.hits);
+    let _ = thread::spawn(move || {
+        let _guard = hits.lock().unwrap();
+        panic!("poison the lock");
+    }).join();
+    assert_eq!(to_string(&counters).unwrap_err().to_string(), "Mutex is poisoned");
+}
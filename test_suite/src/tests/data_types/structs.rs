@@ -156,4 +156,15 @@ fn round_trip_simple_struct() -> Result<(), PError> {
     let parsed: XMLObjectSimple = from_xml(&xml)?;
     assert_eq!(original, parsed);
     Ok(())
-} 
\ No newline at end of file
+} 
+#[test]
+fn round_trip_optional_unit_struct() -> Result<(), PError> {
+    for original in [
+        XMLObjectWithEmptyTag { field_a: "With".to_string(), empty: Some(XMLObjectUnit) },
+        XMLObjectWithEmptyTag { field_a: "Without".to_string(), empty: None },
+    ] {
+        let parsed: XMLObjectWithEmptyTag = from_xml(&from_obj(&original))?;
+        assert_eq!(original, parsed);
+    }
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use xavier::{from_obj, from_xml, namespaces, PError, XmlDeserializable, XmlSerializable};
+use xavier::serialize::namespaces::Namespaces;
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="shape", tag_attribute="xsi:type")]
+enum Shape {
+    #[xml(name="ns:Circle")]
+    Circle { #[xml(attribute)] id: u32, radius: f64 },
+    #[xml(name="ns:Square")]
+    Square { side: f64, label: Option<String> },
+    #[xml(name="ns:Named")]
+    Named(String),
+    #[xml(name="ns:None")]
+    Nothing,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="drawing", tag_attribute="kind", case="Snake")]
+enum Drawing {
+    LineArt { strokes: u32 },
+    Blank,
+}
+
+#[derive(XmlSerializable, XmlDeserializable, Debug, PartialEq)]
+#[xml(name="canvas")]
+struct Canvas {
+    #[xml(tree)]
+    pub shape: Shape,
+    pub drawing: Drawing,
+}
+
+#[derive(XmlSerializable)]
+#[xml(name="gallery")]
+struct Gallery {
+    #[xml(xmlns)]
+    pub namespaces: Namespaces,
+    #[xml(tree)]
+    pub shape: Shape,
+}
+
+#[test]
+fn serialize_tagged_variants() {
+    let canvas = Canvas { shape: Shape::Circle { id: 7, radius: 1.5 }, drawing: Drawing::LineArt { strokes: 3 } };
+    let should = r#"<canvas><shape xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="ns:Circle" id="7"><radius>1.5</radius></shape><drawing><drawing kind="line_art"><strokes>3</strokes></drawing></drawing></canvas>"#;
+    assert_eq!(from_obj(&canvas), should);
+
+    assert_eq!(from_obj(&Shape::Named("a<b".to_string())), r#"<shape xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="ns:Named">a&lt;b</shape>"#);
+    assert_eq!(from_obj(&Shape::Nothing), r#"<shape xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="ns:None"></shape>"#);
+}
+
+#[test]
+fn serialize_xsi_binding_once() {
+    let gallery = Gallery { namespaces: namespaces!(xsi = "http://www.w3.org/2001/XMLSchema-instance"), shape: Shape::Nothing };
+    let should = r#"<gallery xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"><shape xsi:type="ns:None"></shape></gallery>"#;
+    assert_eq!(from_obj(&gallery), should);
+}
+
+#[test]
+fn deserialize_tagged_variants() -> Result<(), PError> {
+    let xml = r#"
+    <canvas xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+        <shape xsi:type="ns:Square"><side>2</side><label>box</label></shape>
+        <drawing><drawing kind="blank"/></drawing>
+    </canvas>"#;
+    let canvas: Canvas = from_xml(xml)?;
+    assert_eq!(canvas.shape, Shape::Square { side: 2.0, label: Some("box".to_string()) });
+    assert_eq!(canvas.drawing, Drawing::Blank);
+    Ok(())
+}
+
+#[test]
+fn tagged_round_trip_as_root() -> Result<(), PError> {
+    let shapes = [
+        Shape::Circle { id: 1, radius: 0.5 },
+        Shape::Square { side: 3.0, label: None },
+        Shape::Named("named".to_string()),
+        Shape::Nothing,
+    ];
+    for shape in shapes {
+        let parsed: Shape = from_xml(&from_obj(&shape))?;
+        assert_eq!(parsed, shape);
+    }
+    Ok(())
+}
+
+#[test]
+fn deserialize_tagged_errors() {
+    let error = from_xml::<Shape>(r#"<shape xsi:type="ns:Hexagon"><side>1</side></shape>"#).unwrap_err();
+    assert!(error.to_string().contains("Unknown xsi:type 'ns:Hexagon' for Shape, expected one of: ns:Circle, ns:Square, ns:Named, ns:None"));
+
+    let error = from_xml::<Canvas>(r#"<canvas><shape><side>1</side></shape><drawing><drawing kind="blank"/></drawing></canvas>"#).unwrap_err();
+    assert!(error.to_string().contains("Attribute 'xsi:type' not found for Shape"));
+}
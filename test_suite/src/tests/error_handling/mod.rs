@@ -5,4 +5,5 @@ pub mod panic_condition_tests;
 pub mod serialization_error_tests;
 pub mod specific_case_error_tests;
 pub mod type_mismatch_errors;
-pub mod validation_error_tests;
\ No newline at end of file
+pub mod validation_error_tests;
+pub mod unknown_field_errors;
\ No newline at end of file
@@ -175,4 +175,19 @@ fn test_custom_naming_handling() -> Result<(), PError> {
     assert_eq!(parsed.age, 25);
     
     Ok(())
-} 
\ No newline at end of file
+} 
+#[test]
+fn test_mixed_visibility_and_nested_options() -> Result<(), PError> {
+    let test_data = TestMixedVisibility {
+        public_field: "public".to_string(),
+        crate_field: "crate".to_string(),
+        private_field: "private".to_string(),
+    };
+    let parsed: TestMixedVisibility = from_xml(&from_obj(&test_data))?;
+    assert_eq!(test_data, parsed);
+
+    let test_data = TestNestedOptions { id: 7, outer: Some("outer".to_string()), inner: None };
+    let parsed: TestNestedOptions = from_xml(&from_obj(&test_data))?;
+    assert_eq!(test_data, parsed);
+    Ok(())
+}
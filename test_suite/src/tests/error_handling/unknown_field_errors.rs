@@ -0,0 +1,63 @@
+use std::io::Cursor;
+use xavier::{from_reader_with, from_xml, from_xml_with, DeserializeOptions, PError, XmlDeserializable};
+
+#[derive(XmlDeserializable, Debug, PartialEq)]
+#[xml(name="config", deny_unknown)]
+struct Config {
+    #[xml(attribute, alias="ver")]
+    pub version: u32,
+    pub name: String,
+    #[xml(inner="path")]
+    pub paths: Vec<String>,
+    pub limits: Option<Limits>,
+}
+
+#[derive(XmlDeserializable, Debug, PartialEq)]
+#[xml(name="limits")]
+struct Limits {
+    #[xml(attribute)]
+    pub unit: Option<String>,
+    pub max: u32,
+}
+
+#[test]
+fn deny_unknown_rejects_unknown_elements() {
+    let xml = r#"<config version="1"><name>app</name><nmae>typo</nmae><paths></paths></config>"#;
+    let error = from_xml::<Config>(xml).unwrap_err();
+    assert!(error.to_string().contains("Unknown element 'nmae' in 'config' at position 42"));
+}
+
+#[test]
+fn deny_unknown_rejects_unknown_attributes() {
+    let xml = r#"<config version="1" verison="2"><name>app</name><paths></paths></config>"#;
+    let error = from_xml::<Config>(xml).unwrap_err();
+    assert!(error.to_string().contains("Unknown attribute 'verison' in 'config'"));
+}
+
+#[test]
+fn deny_unknown_accepts_known_names_and_namespaces() -> Result<(), PError> {
+    let xml = r#"<config ver="2" xmlns="urn:app" xmlns:x="urn:x"><name>app</name><paths><path>/a</path></paths><limits unit="s" extra="1"><limits><max>5</max><other/></limits></limits></config>"#;
+    let config: Config = from_xml(xml)?;
+    assert_eq!(config.version, 2);
+    assert_eq!(config.paths, vec!["/a".to_string()]);
+    // deny_unknown isn't inherited by the field types
+    assert_eq!(config.limits, Some(Limits { unit: Some("s".to_string()), max: 5 }));
+    Ok(())
+}
+
+#[test]
+fn strict_option_applies_to_every_type() -> Result<(), PError> {
+    let xml = r#"<limits unit="s"><max>5</max><min>1</min></limits>"#;
+    assert_eq!(from_xml::<Limits>(xml)?.max, 5);
+
+    let strict = DeserializeOptions::default().strict();
+    let error = from_xml_with::<Limits>(xml, &strict).unwrap_err();
+    assert!(error.to_string().contains("Unknown element 'min' in 'limits'"));
+
+    let error = from_reader_with::<Limits, _>(Cursor::new(r#"<limits size="1"><max>5</max></limits>"#), &strict).unwrap_err();
+    assert!(error.to_string().contains("Unknown attribute 'size' in 'limits'"));
+
+    // The option only lasts for the call
+    assert_eq!(from_xml::<Limits>(xml)?.max, 5);
+    Ok(())
+}
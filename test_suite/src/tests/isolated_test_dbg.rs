@@ -10,7 +10,7 @@ fn isolated_test() -> Result<(), PError> {
 }
 
 // impl TestObject {
-//     pub fn from_xml_dbg(mut reader: &mut ::xavier::quick_xml::Reader<&[u8]>, start_event: Option<&::xavier::quick_xml::events::BytesStart>) -> Result<Option<Self>, xavier::PError> {
+//     pub fn from_xml_dbg<R: std::io::BufRead>(mut reader: &mut ::xavier::quick_xml::Reader<R>, start_event: Option<&::xavier::quick_xml::events::BytesStart>) -> Result<Option<Self>, xavier::PError> {
 //
 //     }
 // }
\ No newline at end of file
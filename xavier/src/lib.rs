@@ -1,3 +1,6 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
 use std::panic;
 use std::panic::{AssertUnwindSafe, PanicHookInfo};
 use std::sync::{Arc, Mutex};
@@ -6,8 +9,17 @@ pub use xavier_derive::XmlSerializable;
 pub use xavier_derive::XmlDeserializable;
 
 pub use xavier_internal::serialize::macro_trait::XmlSerializable;
-pub use xavier_internal::deserialize::macro_trait::XmlDeserializable;
+pub use xavier_internal::serialize::writer::XmlWriter;
+pub use xavier_internal::serialize::options::SerializeOptions;
+pub use xavier_internal::serialize::raw::RawXml;
+pub use xavier_internal::serialize::text::ToXmlText;
+pub use xavier_internal::deserialize::macro_trait::{XmlDeserializable, XmlDeserializableOwned};
 pub use xavier_internal::deserialize::error::PError;
+pub use xavier_internal::deserialize::options::DeserializeOptions;
+pub use xavier_internal::deserialize::text::FromXmlText;
+use xavier_internal::deserialize::encoding::{utf8_bom_length, DecodingReader};
+use xavier_internal::deserialize::source::{OwnedSource, XmlSource};
+use xavier_internal::dom::Element;
 pub use xavier_internal::encode;
 pub use xavier_internal::namespaces;
 pub use xavier_internal::cdata;
@@ -18,27 +30,146 @@ pub use xavier_internal::instructions;
 
 pub use xavier_internal::serialize;
 pub use xavier_internal::deserialize;
+pub use xavier_internal::dom;
 
 pub use ::quick_xml;
 
+/* Panics when the object can't be written: a RefCell already mutably borrowed or a poisoned Mutex/RwLock,
+   to_string returns those as errors */
 pub fn from_obj<T: XmlSerializable>(obj: &T) -> String {
     obj.to_xml(true)
 }
 
-pub fn from_xml<T: XmlDeserializable>(xml: &str) -> Result<T, PError> {
+pub fn from_obj_pretty<T: XmlSerializable>(obj: &T) -> String {
+    from_obj_with(obj, &SerializeOptions::pretty())
+}
+
+/* Panics like from_obj, see to_string_with */
+pub fn from_obj_with<T: XmlSerializable>(obj: &T, options: &SerializeOptions) -> String {
+    to_string_with(obj, options).expect("Writing XML to memory must not fail")
+}
+
+pub fn to_string<T: XmlSerializable>(obj: &T) -> std::io::Result<String> {
+    to_string_with(obj, &SerializeOptions::default())
+}
+
+pub fn to_string_with<T: XmlSerializable>(obj: &T, options: &SerializeOptions) -> std::io::Result<String> {
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut xml_writer = XmlWriter::with_options(&mut buffer, options.clone());
+    obj.write_xml(&mut xml_writer, true)?;
+    String::from_utf8(buffer).map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+}
+
+pub fn to_bytes<T: XmlSerializable>(obj: &T) -> std::io::Result<Vec<u8>> {
+    to_bytes_with(obj, &SerializeOptions::default())
+}
+
+pub fn to_bytes_with<T: XmlSerializable>(obj: &T, options: &SerializeOptions) -> std::io::Result<Vec<u8>> {
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut xml_writer = XmlWriter::transcoding(&mut buffer, options.clone());
+    obj.write_xml(&mut xml_writer, true)?;
+    Ok(buffer)
+}
+
+pub fn to_writer<T: XmlSerializable, W: Write>(obj: &T, writer: W) -> std::io::Result<()> {
+    to_writer_with(obj, writer, &SerializeOptions::default())
+}
+
+/* The sink is buffered here, every tag and text fragment is a separate write */
+pub fn to_writer_with<T: XmlSerializable, W: Write>(obj: &T, writer: W, options: &SerializeOptions) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(writer);
+    let mut xml_writer = XmlWriter::transcoding(&mut writer, options.clone());
+    obj.write_xml(&mut xml_writer, true)?;
+    xml_writer.flush()
+}
+
+pub fn from_xml<'de, T: XmlDeserializable<'de>>(xml: &'de str) -> Result<T, PError> {
     let opt = from_xml_using_builder(xml, T::from_xml)?;
     opt.ok_or_else(|| PError::new("XML cannot be parsed or not found!"))
 }
 
-pub fn from_xml_using_builder<T, B>(xml: &str, builder: B) -> Result<Option<T>, PError>
+pub fn from_xml_with<'de, T: XmlDeserializable<'de>>(xml: &'de str, options: &DeserializeOptions) -> Result<T, PError> {
+    options.scope(|| from_xml(xml))
+}
+
+pub fn from_slice<'de, T: XmlDeserializable<'de>>(xml: &'de [u8]) -> Result<T, PError> {
+    if xml.trim_ascii().is_empty() {
+        return Err(PError::new("Empty XML or whitespace-only content"));
+    }
+    // UTF-8 documents are parsed in place so borrowed fields can point into them
+    let Some(bom_length) = utf8_bom_length(xml)? else {
+        return from_transcoded(xml);
+    };
+    let opt = from_slice_using_builder(&xml[bom_length..], T::from_xml)?;
+    opt.ok_or_else(|| PError::new("XML cannot be parsed or not found!"))
+}
+
+/* Text is copied out of the reader, types borrowing from the input are rejected at compile time */
+pub fn from_reader<T: XmlDeserializableOwned, R: BufRead>(reader: R) -> Result<T, PError> {
+    from_transcoded(reader)
+}
+
+pub fn from_reader_with<T: XmlDeserializableOwned, R: BufRead>(reader: R, options: &DeserializeOptions) -> Result<T, PError> {
+    options.scope(|| from_reader(reader))
+}
+
+pub fn from_file<T: XmlDeserializableOwned, P: AsRef<Path>>(path: P) -> Result<T, PError> {
+    from_reader(BufReader::new(File::open(path)?))
+}
+
+// Decodes the input to UTF-8 first, borrowed fields fall back to owned text here
+fn from_transcoded<'de, T: XmlDeserializable<'de>, R: BufRead>(reader: R) -> Result<T, PError> {
+    let opt = from_reader_using_builder(DecodingReader::new(reader)?, T::from_xml)?;
+    opt.ok_or_else(|| PError::new("XML cannot be parsed or not found!"))
+}
+
+/* Tree of the serialized object, the root element is the object itself */
+pub fn to_dom<T: XmlSerializable>(obj: &T) -> Result<Element, PError> {
+    let mut xml_writer = XmlWriter::tree();
+    obj.write_xml(&mut xml_writer, true)?;
+    xml_writer.into_tree().ok_or_else(|| PError::new("No element was written"))
+}
+
+/* The deserializers read XML events, so the tree is written out and parsed again (one serialize and one parse) */
+pub fn from_dom<T: XmlDeserializableOwned>(element: &Element) -> Result<T, PError> {
+    from_xml(&from_obj(element))
+}
+
+pub fn from_xml_using_builder<'a, T, B>(xml: &'a str, builder: B) -> Result<Option<T>, PError>
 where
-    T: XmlDeserializable,
-    B: Fn(&mut quick_xml::Reader<&[u8]>, Option<&quick_xml::events::BytesStart<'_>>) -> Result<Option<T>, PError>,
+    T: XmlDeserializable<'a>,
+    B: Fn(&mut quick_xml::Reader<&'a [u8]>, Option<&quick_xml::events::BytesStart<'_>>) -> Result<Option<T>, PError>,
 {
     if xml.trim().is_empty() {
         return Err(PError::new("Empty XML or whitespace-only content"));
     }
+    from_slice_using_builder(xml.as_bytes(), builder)
+}
+
+/* In memory UTF-8 input, borrowed fields point into it */
+pub fn from_slice_using_builder<'a, T, B>(xml: &'a [u8], builder: B) -> Result<Option<T>, PError>
+where
+    T: XmlDeserializable<'a>,
+    B: Fn(&mut quick_xml::Reader<&'a [u8]>, Option<&quick_xml::events::BytesStart<'_>>) -> Result<Option<T>, PError>,
+{
+    from_source_using_builder(xml, builder)
+}
+
+pub fn from_reader_using_builder<'de, T, R, B>(source: R, builder: B) -> Result<Option<T>, PError>
+where
+    T: XmlDeserializable<'de>,
+    R: BufRead,
+    B: Fn(&mut quick_xml::Reader<OwnedSource<R>>, Option<&quick_xml::events::BytesStart<'_>>) -> Result<Option<T>, PError>,
+{
+    from_source_using_builder(OwnedSource(source), builder)
+}
 
+fn from_source_using_builder<'de, T, R, B>(source: R, builder: B) -> Result<Option<T>, PError>
+where
+    T: XmlDeserializable<'de>,
+    R: XmlSource<'de>,
+    B: Fn(&mut quick_xml::Reader<R>, Option<&quick_xml::events::BytesStart<'_>>) -> Result<Option<T>, PError>,
+{
     let panic_info = Arc::new(Mutex::new(String::new()));
 
     panic::set_hook(Box::new({
@@ -54,12 +185,14 @@ where
 
     let result = panic::catch_unwind(AssertUnwindSafe( || {
 
-        let mut reader = quick_xml::Reader::from_str(&xml);
+        let mut reader = quick_xml::Reader::from_reader(source);
         reader.config_mut().expand_empty_elements = true;
+        let mut buffer = Vec::new();
         let found_element = false;
 
         loop {
-            match reader.read_event() {
+            buffer.clear();
+            match reader.read_event_into(&mut buffer) {
                 Err(error) =>  {
                     return Err(PError::new(&format!("Error at position {}: {:?}", reader.buffer_position(), error)))
                 },
@@ -95,4 +228,4 @@ where
     } else {
         Err(PError::new("Fail to parse XML, please check the structure and in case of bug please report on GitHub"))
     }
-}
\ No newline at end of file
+}
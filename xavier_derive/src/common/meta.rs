@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use proc_macro2::TokenStream;
-use syn::{Attribute, Error, Ident, LitStr, Meta, Token};
+use quote::quote;
+use syn::{Attribute, Error, Ident, LitStr, Meta, Path, Token};
 use syn::parse::{Parse, ParseStream};
 
 pub enum MetaName {
@@ -21,11 +22,13 @@ impl Display for MetaName {
     }
 }
 
-pub struct MetaInfo(pub HashMap<String, String>);
+/* Last value of each key, and every entry in order for repeatable keys (alias) */
+pub struct MetaInfo(pub HashMap<String, String>, pub Vec<(String, String)>);
 
 impl Parse for MetaInfo {
     fn parse(input: ParseStream) -> Result<MetaInfo, Error> {
         let mut result = HashMap::new();
+        let mut entries = vec![];
         while input.peek(Ident) {
             if input.peek(Ident) {
                 let key: Ident = input.parse()?;
@@ -34,17 +37,15 @@ impl Parse for MetaInfo {
                     let _: Token![=] = input.parse()?;
                     value = input.parse()?;
                 }
-                if let Some(value) = value {
-                    result.insert(key.to_string(), value.value());
-                } else {
-                    result.insert(key.to_string(), "true".to_string());
-                }
+                let value = value.map(|value| value.value()).unwrap_or("true".to_string());
+                entries.push((key.to_string(), value.clone()));
+                result.insert(key.to_string(), value);
             }
             if input.peek(Token![,]) {
                 let _: Token![,] = input.parse()?;
             }
         }
-        Ok(MetaInfo(result))
+        Ok(MetaInfo(result, entries))
     }
 }
 
@@ -54,10 +55,37 @@ impl MetaInfo {
         self.0.get(meta_name).unwrap_or(&default).to_string()
     }
 
+    pub fn all(&self, meta_name: &str) -> Vec<String> {
+        self.1.iter().filter(|(key, _)| key == meta_name).map(|(_, value)| value.clone()).collect()
+    }
+
     pub fn contains(&self, meta_name: &str) -> bool {
         self.0.contains_key(meta_name)
     }
 
+    pub fn path(&self, meta_name: &str) -> Option<Path> {
+        let path = self.0.get(meta_name)?;
+        Some(syn::parse_str(path).unwrap_or_else(|_| panic!("Invalid path in #[xml({})]", meta_name)))
+    }
+
+    /* Custom function for a field, "serialize" looks for serialize_with or with = "module" (module::serialize) */
+    pub fn with_fn(&self, kind: &str) -> Option<Path> {
+        let path = self.0.get(&format!("{}_with", kind)).cloned()
+            .or_else(|| self.0.get("with").map(|module| format!("{}::{}", module, kind)))?;
+        Some(syn::parse_str(&path).expect("Invalid function path in #[xml(with)]"))
+    }
+
+    /* Value for a missing field or container, #[xml(default)] uses Default and default = "fn" calls fn() */
+    pub fn default_value(&self) -> Option<TokenStream> {
+        let value = self.0.get("default")?;
+        if value == "true" {
+            Some(quote! { ::std::default::Default::default() })
+        } else {
+            let path: Path = syn::parse_str(value).expect("Invalid function path in #[xml(default)]");
+            Some(quote! { #path() })
+        }
+    }
+
     pub fn from_name(attrs: &Vec<Attribute>, meta_name: MetaName) -> Option<MetaInfo> {
         let object_attr = MetaInfo::attr_by_name(&attrs, meta_name);
         if let Some(object_attr) = object_attr {
@@ -79,14 +107,14 @@ impl MetaInfo {
     }
 
     pub fn empty() -> MetaInfo {
-        MetaInfo(HashMap::new())
+        MetaInfo(HashMap::new(), vec![])
     }
 
     fn from_attr(attribute: &Attribute) -> MetaInfo {
         if let Meta::List(meta) = &attribute.meta {
             return syn::parse2(TokenStream::from(meta.clone().tokens)).unwrap();
         }
-        MetaInfo(HashMap::new())
+        MetaInfo(HashMap::new(), vec![])
     }
 }
 
@@ -1,8 +1,8 @@
-use syn::{DeriveInput, LitStr};
+use syn::{Data, DeriveInput, Error, Fields, LitStr};
 use convert_case::{Case, Casing};
 use proc_macro2::Ident;
 
-use crate::common::meta::MetaInfo;
+use crate::common::meta::{MetaInfo, MetaName};
 use crate::common::naming::case::CaseFromStr;
 
 
@@ -72,6 +72,62 @@ impl XmlNames {
         LitStr::new(&name, proc_macro2::Span::call_site())
     }
 
+    pub fn variant(variant_name: &Ident, obj_meta: Option<&MetaInfo>, variant_meta: Option<&MetaInfo>) -> LitStr {
+        let empty = MetaInfo::empty();
+        let obj_meta = obj_meta.unwrap_or(&empty);
+        let variant_meta = variant_meta.unwrap_or(&empty);
+
+        let ignore_case = &variant_meta.get_or("ignore_case", "".to_string());
+        let case = if ignore_case == "true" {
+            None
+        } else {
+            Case::value_from_str(&obj_meta.get_or("case", "".to_string()))
+        };
+
+        let name = XmlNames::compose_name(
+            "",
+            &variant_meta.get_or("name", variant_name.to_string()),
+            "",
+            "",
+            false,
+            false,
+            case,
+        );
+        LitStr::new(&name, proc_macro2::Span::call_site())
+    }
+
+    pub fn unit_variants(input: &DeriveInput) -> Result<Vec<(Ident, LitStr)>, Error> {
+        let obj_meta = MetaInfo::from_name(&input.attrs, MetaName::XML);
+        let mut variants = vec![];
+        if let Data::Enum(data) = &input.data {
+            for variant in &data.variants {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(Error::new_spanned(variant, "Only unit variants can be mapped to XML text."));
+                }
+                let variant_meta = MetaInfo::from_name(&variant.attrs, MetaName::XML);
+                variants.push((variant.ident.clone(), XmlNames::variant(&variant.ident, obj_meta.as_ref(), variant_meta.as_ref())));
+            }
+        }
+        Ok(variants)
+    }
+
+    /* Extra names accepted when deserializing, taken as is (no case, prefix or namespace) */
+    pub fn aliases(meta: Option<&MetaInfo>) -> Vec<LitStr> {
+        meta.map(|meta| meta.all("alias")).unwrap_or_default().iter()
+            .map(|alias| LitStr::new(alias, proc_macro2::Span::call_site()))
+            .collect()
+    }
+
+    /* Entry element and key attribute names of a map field, a bare `entry` flag keeps the defaults */
+    pub fn map_entry(field_meta: &MetaInfo) -> (LitStr, LitStr) {
+        let entry = match field_meta.get_or("entry", "true".to_string()).as_str() {
+            "true" => "entry".to_string(),
+            entry => entry.to_string(),
+        };
+        let key = field_meta.get_or("key", "key".to_string());
+        (LitStr::new(&entry, proc_macro2::Span::call_site()), LitStr::new(&key, proc_macro2::Span::call_site()))
+    }
+
     fn compose_name(ns: &str, name: &str, prefix: &str, suffix: &str, use_suffix: bool, use_prefix: bool, case: Option<Case>) -> String {
         let namespace = if !ns.is_empty() {
             ns.to_string() + ":"
@@ -0,0 +1,178 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::{DeriveInput, Error, Fields, FieldsNamed, LitStr};
+use crate::common::meta::{MetaInfo, MetaName};
+use crate::common::naming::names::XmlNames;
+use crate::deserialize::parser::complex::stream::XmlComplex;
+use crate::deserialize::parser::complex::tokens::segments::TokenSegments;
+use crate::deserialize::parser::text::XmlText;
+
+/* Enum with data carrying variants, the element name selects the variant */
+pub struct XmlChoice;
+
+impl XmlChoice {
+    pub fn parse(input: &DeriveInput) -> TokenStream {
+        let obj_meta_info = MetaInfo::from_name(&input.attrs, MetaName::XML);
+        let enum_name = input.ident.to_string();
+
+        let syn::Data::Enum(data) = &input.data else { return quote! {} };
+        let mut names = vec![];
+        let mut direct = vec![];
+        let mut nested = vec![];
+        for variant in &data.variants {
+            let ident = &variant.ident;
+            let variant_meta = MetaInfo::from_name(&variant.attrs, MetaName::XML);
+            if XmlChoice::is_text(variant_meta.as_ref()) {
+                // Only read as mixed content, see mixed_items
+                continue;
+            }
+            let name = XmlNames::variant(ident, obj_meta_info.as_ref(), variant_meta.as_ref());
+            let aliases = XmlNames::aliases(variant_meta.as_ref());
+            let name_matches = quote! { (xa_tag_name == #name #(|| xa_tag_name == #aliases)*) };
+            let not_found = LitStr::new(&format!("Value not found for '{}'", name.value()), Span::call_site());
+
+            let (matches, value) = match &variant.fields {
+                Fields::Unit => (
+                    name_matches,
+                    quote! {{
+                        reader.read_to_end_into(event.name(), &mut Vec::new())?;
+                        Self::#ident
+                    }}
+                ),
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                    let ty = &fields.unnamed[0].ty;
+                    let matches = if variant_meta.map(|meta| meta.contains("tree")).unwrap_or(false) {
+                        quote! { <#ty>::accepts_name(&xa_tag_name) }
+                    } else {
+                        name_matches
+                    };
+                    (matches, quote! {
+                        match <#ty>::from_xml(&mut reader, Some(event))? {
+                            Some(xa_value) => Self::#ident(xa_value),
+                            None => return Err(PError::new(#not_found)),
+                        }
+                    })
+                },
+                Fields::Named(fields) => {
+                    (name_matches, XmlChoice::struct_variant(ident, fields, obj_meta_info.as_ref(), quote! { #name }, &aliases, &not_found))
+                },
+                Fields::Unnamed(_) => {
+                    return Error::new_spanned(variant, "Tuple variants must have a single field.").to_compile_error();
+                }
+            };
+            names.push(name.value());
+            direct.push(quote! {
+                if #matches {
+                    return Ok(Some(#value));
+                }
+            });
+            nested.push(quote! {
+                if #matches {
+                    xa_result = Some(#value);
+                    if xa_wrapper.is_none() {
+                        return Ok(xa_result);
+                    }
+                    continue;
+                }
+            });
+        }
+        let expected = names.join(", ");
+
+        quote! {
+            let mut xa_result: Option<Self> = None;
+            // The start event is either the variant element itself or a wrapper holding it
+            let mut xa_wrapper: Option<Vec<u8>> = None;
+            if let Some(event) = start_event {
+                let xa_tag_name = String::from_utf8(event.name().0.to_vec())?;
+                #(#direct)*
+                xa_wrapper = Some(event.name().0.to_vec());
+            }
+
+            let mut xa_buffer = Vec::new();
+            loop {
+                xa_buffer.clear();
+                match reader.read_event_into(&mut xa_buffer) {
+                    Err(error) =>  { return Err(PError::new(&format!("Error at position {}: {:?}", reader.buffer_position(), error))) },
+                    Ok(::xavier::quick_xml::events::Event::Start(event)) => {
+                        if xa_result.is_some() {
+                            reader.read_to_end_into(event.name(), &mut Vec::new())?;
+                            continue;
+                        }
+                        let event = &event;
+                        let xa_tag_name = String::from_utf8(event.name().0.to_vec())?;
+                        #(#nested)*
+                        return Err(PError::new(&format!("Unexpected element '{}' for {}, expected one of: {}", xa_tag_name, #enum_name, #expected)));
+                    },
+                    Ok(::xavier::quick_xml::events::Event::End(event)) => {
+                        if xa_wrapper.as_deref() == Some(event.name().0) {
+                            return Ok(xa_result);
+                        }
+                    },
+                    Ok(::xavier::quick_xml::events::Event::Eof) => { return Ok(xa_result) },
+                    Ok(::xavier::quick_xml::events::Event::Empty(_)) => {},
+                    Ok(::xavier::quick_xml::events::Event::Text(_)) => {},
+                    Ok(::xavier::quick_xml::events::Event::CData(_)) => {},
+                    Ok(::xavier::quick_xml::events::Event::Decl(_)) => {},
+                    Ok(::xavier::quick_xml::events::Event::PI(_)) => {},
+                    Ok(::xavier::quick_xml::events::Event::DocType(_)) => {},
+                    Ok(::xavier::quick_xml::events::Event::Comment(_)) => {}
+                }
+            }
+        }
+    }
+    /* Trait functions for mixed content, the #[xml(text)] variant holds the text between the element variants */
+    pub fn mixed_items(input: &DeriveInput) -> TokenStream {
+        let obj_meta_info = MetaInfo::from_name(&input.attrs, MetaName::XML);
+        let syn::Data::Enum(data) = &input.data else { return quote! {} };
+        let mut from_text = quote! { Ok(None) };
+        let mut accepts = vec![];
+        for variant in &data.variants {
+            let ident = &variant.ident;
+            let variant_meta = MetaInfo::from_name(&variant.attrs, MetaName::XML);
+            if XmlChoice::is_text(variant_meta.as_ref()) {
+                let Fields::Unnamed(fields) = &variant.fields else {
+                    return Error::new_spanned(variant, "Text variants must have a single field.").to_compile_error();
+                };
+                if fields.unnamed.len() != 1 {
+                    return Error::new_spanned(variant, "Text variants must have a single field.").to_compile_error();
+                }
+                let value = XmlText::parse(&fields.unnamed[0].ty, quote! { &text });
+                from_text = quote! { Ok(Some(Self::#ident(#value))) };
+            } else if let (Fields::Unnamed(fields), true) = (&variant.fields, variant_meta.as_ref().is_some_and(|meta| meta.contains("tree"))) {
+                let ty = &fields.unnamed[0].ty;
+                accepts.push(quote! { <#ty>::accepts_name(name) });
+            } else {
+                let name = XmlNames::variant(ident, obj_meta_info.as_ref(), variant_meta.as_ref());
+                let aliases = XmlNames::aliases(variant_meta.as_ref());
+                accepts.push(quote! { name == #name #(|| name == #aliases)* });
+            }
+        }
+        quote! {
+            fn from_text(text: String) -> Result<Option<Self>, PError> {
+                #from_text
+            }
+            fn accepts_item(name: &str) -> bool {
+                false #(|| #accepts)*
+            }
+        }
+    }
+
+    fn is_text(variant_meta: Option<&MetaInfo>) -> bool {
+        variant_meta.is_some_and(|meta| meta.contains("text"))
+    }
+
+    /* Parses a struct variant from `event` up to the end tag named end_name (or an alias) */
+    pub fn struct_variant(ident: &Ident, fields: &FieldsNamed, obj_meta: Option<&MetaInfo>, end_name: TokenStream, aliases: &[LitStr], not_found: &LitStr) -> TokenStream {
+        let tokens = TokenSegments::tokens_from_fields(fields, obj_meta, quote! { Self::#ident });
+        let body = XmlComplex::body(end_name, aliases, tokens);
+        quote! {{
+            let xa_variant = |mut reader: &mut ::xavier::quick_xml::Reader<XaRead>, start_event: Option<&::xavier::quick_xml::events::BytesStart>| -> Result<Option<Self>, PError> {
+                #body
+            };
+            match xa_variant(&mut *reader, Some(event))? {
+                Some(xa_value) => xa_value,
+                None => return Err(PError::new(#not_found)),
+            }
+        }}
+    }
+}
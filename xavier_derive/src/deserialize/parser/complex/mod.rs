@@ -1,2 +1,2 @@
 pub mod stream;
-mod tokens;
\ No newline at end of file
+pub mod tokens;
\ No newline at end of file
@@ -13,7 +13,13 @@ impl XmlComplex {
         let obj_meta_info = MetaInfo::from_name(&input.attrs, MetaName::XML);
         let xml_tag_name = LitStr::new(&XmlNames::root(&input, obj_meta_info.as_ref()), Span::call_site());
 
+        let aliases = XmlNames::aliases(obj_meta_info.as_ref());
         let tokens = TokenSegments::tokens_from(input, obj_meta_info.as_ref());
+        XmlComplex::body(quote! { #xml_tag_name }, &aliases, tokens)
+    }
+
+    /* Reads attributes from the start event and children until the end tag named xml_tag_name (or an alias) */
+    pub fn body(xml_tag_name: TokenStream, aliases: &[LitStr], tokens: TokenSegments) -> TokenStream {
         let declarations = tokens.declarations;
         let attribute_setters = tokens.attribute_setters;
         let field_setters = tokens.field_setters;
@@ -22,44 +28,153 @@ impl XmlComplex {
         let value_setters = tokens.value_setters;
         let xmlns_setter = tokens.xmlns_setter;
         let constructor =  tokens.constructor;
+        let deny_unknown = tokens.deny_unknown;
+        let known_attributes = tokens.known_attributes;
+        let is_self = quote! { (xa_tag_name == #xml_tag_name #(|| xa_tag_name == #aliases)*) };
+        // Elements no setter claimed, the element itself can show up again when it is wrapped by its field element.
+        // Comments, PIs and text go with them, text only when no value or mixed field reads it and not just layout whitespace
+        let captures_text = value_setters.is_empty() && tokens.mixed.is_none();
+        let (unknown_element, unknown_text, unknown_cdata, unknown_comment, unknown_pi) = match &tokens.other_elements {
+            Some(field) => (
+                quote! {
+                    if !#is_self {
+                        if let Some(xa_node) = xavier::dom::XmlNode::from_xml(&mut reader, Some(&event))? {
+                            #field.get_or_insert_with(Vec::new).push(xa_node);
+                        }
+                        continue;
+                    }
+                },
+                if captures_text {
+                    quote! {
+                        let xa_text = xavier::deserialize::text::decode_text(&event)?;
+                        if !xa_text.trim().is_empty() {
+                            #field.get_or_insert_with(Vec::new).push(xavier::dom::XmlNode::Text(xa_text));
+                        }
+                    }
+                } else {
+                    quote! {}
+                },
+                if captures_text {
+                    quote! { #field.get_or_insert_with(Vec::new).push(xavier::dom::XmlNode::CData(xavier::deserialize::text::decode_cdata(&event)?)); }
+                } else {
+                    quote! {}
+                },
+                quote! { #field.get_or_insert_with(Vec::new).push(xavier::dom::XmlNode::Comment(String::from_utf8(event.to_vec())?)); },
+                quote! { #field.get_or_insert_with(Vec::new).push(xavier::dom::XmlNode::PI(String::from_utf8(event.to_vec())?)); },
+            ),
+            None => (quote! {
+                if xa_strict && !#is_self {
+                    return Err(PError::new(&format!("Unknown element '{}' in '{}' at position {}", xa_tag_name, #xml_tag_name, reader.buffer_position())));
+                }
+            }, quote! {}, quote! {}, quote! {}, quote! {}),
+        };
+        let (mixed_element, mixed_text, mixed_cdata) = match &tokens.mixed {
+            Some((field, item_type)) => (
+                quote! {
+                    if <#item_type>::accepts_item(&xa_tag_name) {
+                        if let Some(xa_item) = <#item_type>::from_xml(&mut reader, Some(&event))? {
+                            #field.get_or_insert_with(Vec::new).push(xa_item);
+                        }
+                        continue;
+                    }
+                },
+                quote! {
+                    if let Some(xa_item) = <#item_type>::from_text(xavier::deserialize::text::decode_text(&event)?)? {
+                        #field.get_or_insert_with(Vec::new).push(xa_item);
+                    }
+                },
+                quote! {
+                    if let Some(xa_item) = <#item_type>::from_text(xavier::deserialize::text::decode_cdata(&event)?)? {
+                        #field.get_or_insert_with(Vec::new).push(xa_item);
+                    }
+                },
+            ),
+            None => (quote! {}, quote! {}, quote! {}),
+        };
+        // Character data of value fields, it is gathered up to the end tag and text before a child element is dropped
+        let (value_declaration, value_start, value_text, value_cdata, value_end) = if !value_setters.is_empty() {
+            (
+                quote! { let mut xa_value_text: Option<String> = None; },
+                quote! { xa_value_text = None; },
+                quote! { xa_value_text.get_or_insert_with(String::new).push_str(&xavier::deserialize::text::decode_text(&event)?); },
+                quote! { xa_value_text.get_or_insert_with(String::new).push_str(&xavier::deserialize::text::decode_cdata(&event)?); },
+                quote! {
+                    if let Some(xa_value_text) = &xa_value_text {
+                        #(#value_setters)*
+                    }
+                },
+            )
+        } else {
+            (quote! {}, quote! {}, quote! {}, quote! {}, quote! {})
+        };
+        // Namespace declarations are only claimed by an xmlns field
+        let claims_xmlns = xmlns_setter.is_some();
+        let unknown_attribute = match &tokens.other_attributes {
+            Some(field) => quote! {
+                if !(#claims_xmlns && xa_attr_name.starts_with("xmlns")) #(&& xa_attr_name != #known_attributes)* {
+                    #field.get_or_insert_with(Vec::new).push((xa_attr_name.clone(), xa_attr_value.clone()));
+                }
+            },
+            None => quote! {
+                if xa_strict && !xa_attr_name.starts_with("xmlns") #(&& xa_attr_name != #known_attributes)* {
+                    return Err(PError::new(&format!("Unknown attribute '{}' in '{}' at position {}", xa_attr_name, #xml_tag_name, reader.buffer_position())));
+                }
+            },
+        };
         let gen = quote! {
 
             #(#declarations)*
+            #value_declaration
+            let xa_strict = #deny_unknown || xavier::deserialize::options::is_strict();
 
             if let Some(start_event) = start_event {
                 for xa_attribute in start_event.attributes() {
                     let xa_attr_name = String::from_utf8(xa_attribute.as_ref()?.key.0.to_vec())?;
-                    let xa_attr_value = xavier::deserialize::decode::decode_xml(&String::from_utf8(xa_attribute.as_ref()?.value.to_vec())?);
+                    let xa_attr_value = xavier::deserialize::text::decode_text(&xa_attribute.as_ref()?.value)?;
 
                     #(#attribute_setters)*
                     #xmlns_setter
+                    #unknown_attribute
                 }
             }
 
+            let mut xa_buffer = Vec::new();
             loop {
-                match reader.read_event() {
+                xa_buffer.clear();
+                match reader.read_event_into(&mut xa_buffer) {
                     Err(error) =>  { return Err(PError::new(&format!("Error at position {}: {:?}", reader.buffer_position(), error))) },
                     Ok(::xavier::quick_xml::events::Event::Start(event)) => {
                         let xa_tag_name = String::from_utf8(event.name().0.to_vec())?;
+                        #value_start
 
                         #(#field_setters)*
                         #(#sibling_setters)*
                         #(#inner_setters)*
+                        #mixed_element
+                        #unknown_element
                     },
                     Ok(::xavier::quick_xml::events::Event::Empty(event)) => {
                         let xa_tag_name = String::from_utf8(event.name().0.to_vec())?;
                         #(#field_setters)*
                         #(#sibling_setters)*
                         #(#inner_setters)*
+                        #mixed_element
+                        #unknown_element
                     },
                     Ok(::xavier::quick_xml::events::Event::Text(event)) => {
-                        #(#value_setters)*
+                        #value_text
+                        #mixed_text
+                        #unknown_text
                     },
                     Ok(::xavier::quick_xml::events::Event::CData(event)) => {
-                        #(#value_setters)*
+                        #value_cdata
+                        #mixed_cdata
+                        #unknown_cdata
                     },
                     Ok(::xavier::quick_xml::events::Event::End(event)) => {
-                        if String::from_utf8(event.name().0.to_vec())? == #xml_tag_name {
+                        let xa_end_name = String::from_utf8(event.name().0.to_vec())?;
+                        if xa_end_name == #xml_tag_name #(|| xa_end_name == #aliases)* {
+                            #value_end
 
                             #constructor
                         } else {
@@ -68,9 +183,13 @@ impl XmlComplex {
                     },
                     Ok(::xavier::quick_xml::events::Event::Eof) => { break },
                     Ok(::xavier::quick_xml::events::Event::Decl(_)) => {},
-                    Ok(::xavier::quick_xml::events::Event::PI(_)) => {},
+                    Ok(::xavier::quick_xml::events::Event::PI(event)) => {
+                        #unknown_pi
+                    },
                     Ok(::xavier::quick_xml::events::Event::DocType(_)) => {},
-                    Ok(::xavier::quick_xml::events::Event::Comment(_)) => {}
+                    Ok(::xavier::quick_xml::events::Event::Comment(event)) => {
+                        #unknown_comment
+                    }
                 };
             };
             Err(xavier::PError::new("Error root not found"))
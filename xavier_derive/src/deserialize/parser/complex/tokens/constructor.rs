@@ -3,11 +3,20 @@ use quote::{quote, ToTokens};
 
 pub struct ConstructorField {
     pub path_idents: Vec<Ident>,
-    pub field: Ident
+    pub field: Ident,
+    /* Builds the collection from the gathered items (xa_items) */
+    pub collect: Option<TokenStream>,
+    /* #[xml(default)] value used when the field is missing */
+    pub default: Option<TokenStream>,
+    /* #[xml(skip)] or skip_deserializing, never read and always built from its default */
+    pub skipped: bool,
 }
 
 pub struct Constructor {
-    pub values: Vec<ConstructorField>
+    pub path: TokenStream,
+    pub values: Vec<ConstructorField>,
+    /* Container #[xml(default)], missing fields are taken from it (xa_default) */
+    pub default: Option<TokenStream>,
 }
 
 impl ToTokens for Constructor {
@@ -17,6 +26,13 @@ impl ToTokens for Constructor {
 
             let field = item.field.clone();
 
+            if item.skipped {
+                let default = item.default.clone()
+                    .or_else(|| self.default.as_ref().map(|_| quote! { xa_default.#field }))
+                    .unwrap_or_else(|| quote! { ::std::default::Default::default() });
+                return quote! { #field : #default };
+            }
+
             let path_idents = item.path_idents.clone();
             let mut var_field= quote! { #field };
             let has_option = path_idents.iter().any(|ident| ident.to_string() == "Option");
@@ -35,34 +51,37 @@ impl ToTokens for Constructor {
                     var_field = quote! {  Box::new(#var_field) }
                 }
 
-                if ident.to_string() == "Rc" {
-                    var_field = quote! {  Rc::new(#var_field) }
-                }
-
-                if ident.to_string() == "Arc" {
-                    var_field = quote! {  Arc::new(#var_field) }
-                }
-
-                if ident.to_string() == "RefCell" {
-                    var_field = quote! {  RefCell::new(#var_field) }
-                }
-
-                if ident.to_string() == "Mutex" {
-                    var_field = quote! {  Mutex::new(#var_field) }
-                }
-
-                if ident.to_string() == "RwLock" {
-                    var_field = quote! {  RwLock::new(#var_field) }
-                }
-
                 if ident.to_string() == "Option" {
                     var_field = quote! { xavier::deserialize::macro_trait::WrapWith::wrap(#field) };
                 }
             }
 
+            let default = item.default.clone()
+                .or_else(|| self.default.as_ref().map(|_| quote! { xa_default.#field }));
+            if let Some(default) = default {
+                var_field = quote! { if #field.is_none() { #default } else { #var_field } };
+            }
+
             var_field = quote! { #field : #var_field };
             var_field
         }).collect();
-        tokens.extend(quote! { return Ok(Some(Self{ #(#values,)* })); })
+        let collected = self.values.iter().filter_map(|item| {
+            let field = &item.field;
+            item.collect.as_ref().map(|collect| quote! {
+                let #field = match #field {
+                    Some(xa_items) => Some(#collect),
+                    None => None,
+                };
+            })
+        });
+        let path = &self.path;
+        let container_default = self.default.as_ref().map(|default| quote! {
+            let xa_default: Self = #default;
+        });
+        tokens.extend(quote! {
+            #(#collected)*
+            #container_default
+            return Ok(Some(#path { #(#values,)* }));
+        })
     }
 }
\ No newline at end of file
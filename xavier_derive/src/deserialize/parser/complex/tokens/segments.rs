@@ -1,7 +1,7 @@
-use proc_macro2::Ident;
+use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 use syn::Data::Struct;
-use syn::{DeriveInput, Fields};
+use syn::{parse_quote, DeriveInput, Fields, FieldsNamed, LitStr, Type};
 use crate::common::meta::{MetaInfo, MetaName};
 use crate::common::naming::names::XmlNames;
 use crate::deserialize::parser::complex::tokens::constructor::{Constructor, ConstructorField};
@@ -22,12 +22,32 @@ pub struct TokenSegments {
     pub inner_setters: Vec<InnerSetter>,
     pub value_setters: Vec<ValueSetter>,
     pub xmlns_setter: Option<FieldXmlnsSetter>,
-    pub constructor: Constructor
+    pub constructor: Constructor,
+    /* #[xml(deny_unknown)], elements and attributes no setter reads are errors */
+    pub deny_unknown: bool,
+    pub known_attributes: Vec<LitStr>,
+    /* #[xml(other_elements)] and #[xml(other_attributes)] fields, they collect what no setter claims */
+    pub other_elements: Option<Ident>,
+    pub other_attributes: Option<Ident>,
+    /* #[xml(mixed)] field and its item type, it gets the text and the child elements the item accepts in order */
+    pub mixed: Option<(Ident, Type)>,
 }
 
 impl TokenSegments {
 
     pub fn tokens_from(input: &DeriveInput, obj_meta_info: Option<&MetaInfo>) -> TokenSegments {
+        if let Struct(struct_item) = &input.data {
+            if let Fields::Named(fields) = &struct_item.fields {
+                let mut segments = TokenSegments::tokens_from_fields(fields, obj_meta_info, quote! { Self });
+                segments.constructor.default = obj_meta_info.and_then(|meta| meta.default_value());
+                return segments;
+            }
+        }
+        TokenSegments::tokens_from_fields(&parse_quote!({}), obj_meta_info, quote! { Self })
+    }
+
+    /* The path is what gets constructed at the end tag, Self for structs or Self::Variant */
+    pub fn tokens_from_fields(fields: &FieldsNamed, obj_meta_info: Option<&MetaInfo>, path: TokenStream) -> TokenSegments {
 
         let mut declarations: Vec<FieldDecl> = vec![];
 
@@ -37,83 +57,148 @@ impl TokenSegments {
         let mut attribute_setters: Vec<FieldAttributeSetter> = vec![];
         let mut value_setters: Vec<ValueSetter> = vec![];
         let mut xmlns_setter: Option<FieldXmlnsSetter> = None;
+        let mut other_elements: Option<Ident> = None;
+        let mut other_attributes: Option<Ident> = None;
+        let mut mixed: Option<(Ident, Type)> = None;
+        // Struct variants of attribute tagged enums also carry the tag attribute
+        let mut known_attributes: Vec<LitStr> = obj_meta_info
+            .filter(|meta| meta.contains("tag_attribute"))
+            .map(|meta| vec![LitStr::new(&meta.get_or("tag_attribute", "type".to_string()), proc_macro2::Span::call_site())])
+            .unwrap_or_default();
 
         let mut constructors: Vec<ConstructorField> = vec![];
         let mut field_names: Vec<Ident> = vec![];
 
-        if let Struct(struct_item) = &input.data {
-            if let Fields::Named(fields) = &struct_item.fields {
+        for field in fields.named.iter() {
+
+            if let Some(ident) = &field.ident {
 
-                for field in fields.named.iter() {
-
-                    if let Some(ident) = &field.ident {
-
-                        let field_meta = MetaInfo::from_name(&field.attrs, MetaName::XML).unwrap_or(MetaInfo::empty());
-                        let inner_type = TypeParser::unbox_and_unwrap_type(&field.ty);
-                        let is_flatten = field_meta.contains("tree") || field_meta.contains("flatten");
-                        let is_sibling = TypeParser::is_vec(&field.ty) && is_flatten;
-
-                        let optional_type = if field_meta.contains("inner") && TypeParser::is_vec(&field.ty) {
-                            quote! { Option<#inner_type> }
-                        } else {
-                            quote! { Option<#inner_type> }
-                        };
-                        
-                        declarations.push(FieldDecl {
-                            name: ident.clone(),
-                            optional_type,
-                        });
-
-                        if field_meta.contains("attribute") {
-                            let field_attr_name = XmlNames::attribute(&ident, obj_meta_info, &field_meta);
-                            attribute_setters.push(FieldAttributeSetter {
-                                is_string: TypeParser::is_string_type(&inner_type),
-                                name: ident.clone(),
-                                attr_name: field_attr_name
-                            });
-                        } else if field_meta.contains("xmlns") {
-                            xmlns_setter = Some(FieldXmlnsSetter { field: ident.clone() })
-                        } else if field_meta.contains("value") {
-                            value_setters.push(ValueSetter { field: ident.clone(), unwrapped_type: TypeParser::unwrapped_type(&field.ty) })
-                        } else if field_meta.contains("inner") && TypeParser::is_vec(&field.ty) {
-                            let inner_tag_name = field_meta.get_or("inner", "item".to_string());
-                            let inner_tag_lit = syn::LitStr::new(&inner_tag_name, proc_macro2::Span::call_site());
-                            inner_setters.push(InnerSetter {
-                                name: ident.clone(),
-                                inner_type: TypeParser::ty_from_vec(&TypeParser::unbox_and_unwrap_type(&field.ty)),
-                                inner_tag_name: inner_tag_lit,
-                            });
-                            let field_tag_name = XmlNames::tag(&ident, obj_meta_info, Some(&field_meta));
-                            field_setters.push(FieldSetter {
-                                name: ident.clone(),
-                                is_flatten: false,
-                                tag_name: field_tag_name,
-                                inner_type: TypeParser::unbox_and_unwrap_type(&field.ty),
-                            });
-                        } else if is_sibling {
-                            sibling_setters.push(SiblingSetter {
-                                name: ident.clone(),
-                                inner_type: TypeParser::ty_from_vec(&TypeParser::unbox_and_unwrap_type(&field.ty)),
-                            });
-                        } else {
-                            let field_tag_name = XmlNames::tag(&ident, obj_meta_info, Some(&field_meta));
-                            field_setters.push(FieldSetter {
-                                name: ident.clone(),
-                                is_flatten,
-                                tag_name: field_tag_name,
-                                inner_type: TypeParser::unbox_and_unwrap_type(&field.ty),
-                            });
-                        }
-
-                        field_names.push(ident.clone());
-                        constructors.push(ConstructorField {
-                            path_idents: TypeParser::type_path_idents(&field.ty),
-                            field: ident.clone(),
-                        })
-                    }
+                let field_meta = MetaInfo::from_name(&field.attrs, MetaName::XML).unwrap_or(MetaInfo::empty());
+
+                if field_meta.contains("skip") || field_meta.contains("skip_deserializing") {
+                    constructors.push(ConstructorField {
+                        path_idents: vec![],
+                        field: ident.clone(),
+                        collect: None,
+                        default: field_meta.default_value(),
+                        skipped: true,
+                    });
+                    continue;
+                }
+
+                let inner_type = TypeParser::unbox_and_unwrap_type(&field.ty);
+                let is_flatten = field_meta.contains("tree") || field_meta.contains("flatten");
+                let is_custom_collection = field_meta.contains("collection");
+                let with = field_meta.with_fn("deserialize");
+                let item_type: Option<Type> = if with.is_some() {
+                    None
+                } else if is_custom_collection {
+                    Some(parse_quote! { <#inner_type as ::std::iter::IntoIterator>::Item })
+                } else {
+                    TypeParser::collection_item(&inner_type)
+                };
+                let is_sibling = item_type.is_some() && is_flatten;
+                let is_inner = item_type.is_some() && field_meta.contains("inner");
+                let is_mixed = item_type.is_some() && field_meta.contains("mixed");
+
+                // Sibling, inner and custom collections gather their items first and are built by the constructor
+                let collect = match &item_type {
+                    Some(_) if is_custom_collection => Some(quote! { xa_items.into_iter().collect::<#inner_type>() }),
+                    Some(_) if is_sibling || is_inner || is_mixed => Some(quote! { <#inner_type as xavier::deserialize::collections::XmlCollection>::from_items(xa_items)? }),
+                    _ => None,
+                };
+                let optional_type = match (&item_type, &collect) {
+                    (Some(item_type), Some(_)) => quote! { Option<Vec<#item_type>> },
+                    _ => quote! { Option<#inner_type> },
+                };
+
+                declarations.push(FieldDecl {
+                    name: ident.clone(),
+                    optional_type,
+                });
+
+                let is_other = field_meta.contains("other_elements") || field_meta.contains("other_attributes") || is_mixed;
+                if let (true, Some(item_type)) = (is_mixed, &item_type) {
+                    mixed = Some((ident.clone(), item_type.clone()));
+                } else if field_meta.contains("other_elements") {
+                    other_elements = Some(ident.clone());
+                } else if field_meta.contains("other_attributes") {
+                    other_attributes = Some(ident.clone());
+                } else if field_meta.contains("attribute") {
+                    let field_attr_name = XmlNames::attribute(&ident, obj_meta_info, &field_meta);
+                    known_attributes.push(field_attr_name.clone());
+                    known_attributes.extend(XmlNames::aliases(Some(&field_meta)));
+                    attribute_setters.push(FieldAttributeSetter {
+                        is_string: TypeParser::is_string_like(&inner_type),
+                        ty: inner_type.clone(),
+                        name: ident.clone(),
+                        attr_name: field_attr_name,
+                        aliases: XmlNames::aliases(Some(&field_meta)),
+                        with,
+                    });
+                } else if field_meta.contains("xmlns") {
+                    xmlns_setter = Some(FieldXmlnsSetter { field: ident.clone() })
+                } else if field_meta.contains("value") {
+                    value_setters.push(ValueSetter { field: ident.clone(), unwrapped_type: TypeParser::unwrapped_type(&field.ty), is_string: TypeParser::is_string_like(&inner_type), with })
+                } else if let (true, Some(item_type)) = (is_inner, &item_type) {
+                    let inner_tag_name = field_meta.get_or("inner", "item".to_string());
+                    let inner_tag_lit = syn::LitStr::new(&inner_tag_name, proc_macro2::Span::call_site());
+                    inner_setters.push(InnerSetter {
+                        name: ident.clone(),
+                        inner_type: item_type.clone(),
+                        inner_tag_name: inner_tag_lit,
+                    });
+                    let field_tag_name = XmlNames::tag(&ident, obj_meta_info, Some(&field_meta));
+                    field_setters.push(FieldSetter {
+                        name: ident.clone(),
+                        is_flatten: false,
+                        tag_name: field_tag_name,
+                        aliases: XmlNames::aliases(Some(&field_meta)),
+                        inner_type: parse_quote! { Vec<#item_type> },
+                        map_entry: None,
+                        borrow: false,
+                        with: None,
+                    });
+                } else if let (true, Some(item_type)) = (is_sibling, &item_type) {
+                    sibling_setters.push(SiblingSetter {
+                        name: ident.clone(),
+                        inner_type: item_type.clone(),
+                    });
+                } else {
+                    let field_tag_name = XmlNames::tag(&ident, obj_meta_info, Some(&field_meta));
+                    field_setters.push(FieldSetter {
+                        name: ident.clone(),
+                        is_flatten,
+                        tag_name: field_tag_name,
+                        aliases: XmlNames::aliases(Some(&field_meta)),
+                        inner_type: match (&item_type, &collect) {
+                            (Some(item_type), Some(_)) => parse_quote! { Vec<#item_type> },
+                            _ => inner_type.clone(),
+                        },
+                        map_entry: field_meta.contains("entry").then(|| XmlNames::map_entry(&field_meta)),
+                        borrow: with.is_none() && TypeParser::is_borrowed_cow(&inner_type),
+                        with,
+                    });
                 }
+
+                field_names.push(ident.clone());
+                constructors.push(ConstructorField {
+                    path_idents: TypeParser::type_path_idents(&field.ty),
+                    field: ident.clone(),
+                    collect,
+                    default: field_meta.default_value().or_else(|| is_other.then(|| quote! { ::std::default::Default::default() })),
+                    skipped: false,
+                })
             }
         }
-        Self { declarations, field_setters, sibling_setters, inner_setters, attribute_setters, value_setters, xmlns_setter, constructor: Constructor { values: constructors } }
+        Self {
+            declarations, field_setters, sibling_setters, inner_setters, attribute_setters, value_setters, xmlns_setter,
+            constructor: Constructor { path, values: constructors, default: None },
+            deny_unknown: obj_meta_info.is_some_and(|meta| meta.contains("deny_unknown")),
+            known_attributes,
+            other_elements,
+            other_attributes,
+            mixed,
+        }
     }
 }
@@ -1,27 +1,44 @@
 use proc_macro2::{Ident, TokenStream};
 use quote::{quote, ToTokens};
-use syn::LitStr;
+use syn::{Error, LitStr, Path, Type};
+use crate::deserialize::parser::text::XmlText;
 
+/* Attribute values are read from the start event, which doesn't live for the input lifetime,
+   so they can't borrow: Cow<'a, str> attributes are always owned and &'a str is rejected */
 pub struct FieldAttributeSetter {
     pub is_string: bool,
+    pub ty: Type,
     pub name: Ident,
     pub attr_name: LitStr,
+    pub aliases: Vec<LitStr>,
+    pub with: Option<Path>,
 }
 
 impl ToTokens for FieldAttributeSetter {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let attr_name = &self.attr_name;
         let field = &self.name;
-        if self.is_string {
+        let aliases = &self.aliases;
+        if let Some(with) = &self.with {
             tokens.extend(quote! {
-                if xa_attr_name == #attr_name {
-                     #field = Some(xa_attr_value.clone());
+                if xa_attr_name == #attr_name #(|| xa_attr_name == #aliases)* {
+                    #field = Some(#with(&xa_attr_value)?);
+                }
+            })
+        } else if matches!(self.ty, Type::Reference(_)) {
+            let message = "Attributes can't borrow from the input, use String or Cow<'a, str> (always owned) instead of a reference.";
+            tokens.extend(Error::new_spanned(&self.ty, message).to_compile_error())
+        } else if self.is_string {
+            tokens.extend(quote! {
+                if xa_attr_name == #attr_name #(|| xa_attr_name == #aliases)* {
+                     #field = Some(xa_attr_value.clone().into());
                 }
             })
         } else {
+            let value = XmlText::parse(&self.ty, quote! { &xa_attr_value });
             tokens.extend(quote! {
-                if xa_attr_name == #attr_name {
-                    #field = Some(xa_attr_value.parse()?);
+                if xa_attr_name == #attr_name #(|| xa_attr_name == #aliases)* {
+                    #field = Some(#value);
                 }
             })
         }
@@ -1,14 +1,21 @@
 use proc_macro2::{Ident, TokenStream};
 use quote::{quote, ToTokens};
 use syn::LitStr;
-use syn::Type;
+use syn::{Path, Type};
 
 
 pub struct FieldSetter {
     pub is_flatten: bool,
     pub name: Ident,
     pub tag_name: LitStr,
+    pub aliases: Vec<LitStr>,
     pub inner_type: Type,
+    /* Entry element and key attribute names when the field is a map in the entry layout */
+    pub map_entry: Option<(LitStr, LitStr)>,
+    /* Cow<'a, str> fields borrow from in memory inputs */
+    pub borrow: bool,
+    /* #[xml(deserialize_with)] function, it gets the element text */
+    pub with: Option<Path>,
 }
 
 impl ToTokens for FieldSetter {
@@ -17,23 +24,29 @@ impl ToTokens for FieldSetter {
         let tag_name = &self.tag_name;
         let field = &self.name;
         let ty = &self.inner_type;
+        let aliases = &self.aliases;
         if self.is_flatten {
             tokens.extend(quote! {
-                let should_parse = if let Some(inner_name) = #ty::inner_name() {
-                    xa_tag_name == inner_name && #field.is_none()
-                } else {
-                    false
-                };
+                let should_parse = <#ty>::accepts_name(&xa_tag_name) && #field.is_none();
             });
         } else {
             tokens.extend(quote! {
-                let should_parse = xa_tag_name == #tag_name;
+                let should_parse = xa_tag_name == #tag_name #(|| xa_tag_name == #aliases)*;
             });
         }
 
+        let parse = if let Some(with) = &self.with {
+            quote! { xavier::deserialize::primitives::read_with(&mut reader, Some(&event), #with) }
+        } else if self.borrow {
+            quote! { xavier::deserialize::primitives::read_borrowed_text(&mut reader) }
+        } else if let Some((entry_name, key_name)) = &self.map_entry {
+            quote! { xavier::deserialize::map::read_entries::<#ty, _>(&mut reader, &event, #entry_name, #key_name).map(Some) }
+        } else {
+            quote! { <#ty>::from_xml(&mut reader, Some(&event)) }
+        };
         tokens.extend(quote! {
             if should_parse {
-                match #ty::from_xml(&mut reader, Some(&event)) {
+                match #parse {
                     Ok(t_value) => { #field = t_value; continue; },
                     Err(err) => return Err(PError::new(&format!("Error parsing XML: {:?}", err))),
                 }
@@ -17,7 +17,7 @@ impl ToTokens for InnerSetter {
 
         tokens.extend(quote! {
             if xa_tag_name == #inner_tag {
-                match #ty::from_xml(&mut reader, Some(&event)) {
+                match <#ty>::from_xml(&mut reader, Some(&event)) {
                     Ok(t_value) => {
                         #field.get_or_insert_with(Vec::new).push(t_value.unwrap());
                         continue;
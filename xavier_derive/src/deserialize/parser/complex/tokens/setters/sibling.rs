@@ -15,13 +15,8 @@ impl ToTokens for SiblingSetter {
         let ty = &self.inner_type;
 
         tokens.extend(quote! {
-            let should_parse = if let Some(inner_name) = #ty::inner_name() {
-                xa_tag_name == inner_name
-            } else {
-                false
-            };
-            if should_parse {
-                match #ty::from_xml(&mut reader, Some(&event)) {
+            if <#ty>::accepts_name(&xa_tag_name) {
+                match <#ty>::from_xml(&mut reader, Some(&event)) {
                     Ok(t_value) => {
                         #field.get_or_insert_with(Vec::new).push(t_value.unwrap());
                         continue;
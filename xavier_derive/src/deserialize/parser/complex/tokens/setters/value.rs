@@ -1,11 +1,15 @@
 use proc_macro2::{Ident, TokenStream};
 use quote::{quote, ToTokens};
-use syn::Type;
+use syn::{Path, Type};
+use crate::deserialize::parser::text::XmlText;
 
 
+/* Sets a #[xml(value)] field from the character data gathered in xa_value_text, run at the end tag */
 pub struct ValueSetter {
     pub field: Ident,
-    pub unwrapped_type: Type
+    pub unwrapped_type: Type,
+    pub is_string: bool,
+    pub with: Option<Path>,
 }
 
 impl ToTokens for ValueSetter {
@@ -14,9 +18,22 @@ impl ToTokens for ValueSetter {
         let field = &self.field;
         let ty = &self.unwrapped_type;
 
-        tokens.extend(quote! {
-            let result: #ty = String::from_utf8(event.to_vec())?.parse()?;
-            #field = Some(result);
-        })
+        if let Some(with) = &self.with {
+            tokens.extend(quote! {
+                let result: #ty = #with(xa_value_text)?;
+                #field = Some(result);
+            })
+        } else if self.is_string {
+            tokens.extend(quote! {
+                let result: #ty = xa_value_text.clone().into();
+                #field = Some(result);
+            })
+        } else {
+            let value = XmlText::parse(ty, quote! { xa_value_text });
+            tokens.extend(quote! {
+                let result: #ty = #value;
+                #field = Some(result);
+            })
+        }
     }
 }
\ No newline at end of file
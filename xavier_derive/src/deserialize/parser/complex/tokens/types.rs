@@ -10,6 +10,9 @@ impl TypeParser {
     pub fn type_path_idents(ty: &Type) -> Vec<Ident> {
         let mut idents = Vec::new();
         if let Type::Path(TypePath { path, .. }) = ty {
+            if TypeParser::is_boxed_slice(ty) {
+                return idents;
+            }
             for segment in &path.segments {
                 if &segment.ident == "Option" || &segment.ident == "Box" {
                     idents.push(segment.ident.clone());
@@ -26,10 +29,32 @@ impl TypeParser {
         idents
     }
 
-    pub fn is_vec(ty: &Type) -> bool {
-        if let Type::Path(type_path) = ty {
-            if let Some(path) = &type_path.path.segments.first() {
-                return path.ident == "Vec";
+    /* Item type of the collections with a runtime XmlCollection impl (Vec, sets, arrays, Box<[T]>...) */
+    pub fn collection_item(ty: &Type) -> Option<Type> {
+        match ty {
+            Type::Array(array) => Some(*array.elem.clone()),
+            Type::Path(type_path) => {
+                let segment = type_path.path.segments.last()?;
+                let name = segment.ident.to_string();
+                if name == "Box" {
+                    if let Some(Type::Slice(slice)) = Self::type_from_segment_args(segment) {
+                        return Some(*slice.elem);
+                    }
+                    None
+                } else if ["Vec", "VecDeque", "LinkedList", "HashSet", "BTreeSet"].contains(&name.as_str()) {
+                    Self::type_from_segment_args(segment)
+                } else {
+                    None
+                }
+            },
+            _ => None,
+        }
+    }
+
+    pub fn is_boxed_slice(ty: &Type) -> bool {
+        if let Some(segment) = Self::first_path_segment(ty) {
+            if segment.ident == "Box" {
+                return matches!(Self::type_from_segment_args(&segment), Some(Type::Slice(_)));
             }
         }
         false
@@ -46,6 +71,33 @@ impl TypeParser {
         }
     }
 
+    /* String or a shared str (Cow<str>, Rc<str>, Arc<str>), built from the text with into() */
+    pub fn is_string_like(ty: &Type) -> bool {
+        if Self::is_string_type(ty) {
+            return true;
+        }
+        if let Some(segment) = Self::first_path_segment(ty) {
+            if ["Cow", "Rc", "Arc"].contains(&segment.ident.to_string().as_str()) {
+                if let AngleBracketed(args) = &segment.arguments {
+                    return args.args.iter().any(|arg| matches!(arg, GenericArgument::Type(inner) if quote::quote!(#inner).to_string() == "str"));
+                }
+            }
+        }
+        false
+    }
+
+    /* Cow<'a, str> with a lifetime of the type, it can borrow from the input */
+    pub fn is_borrowed_cow(ty: &Type) -> bool {
+        if let Some(segment) = Self::first_path_segment(ty) {
+            if segment.ident == "Cow" && Self::is_string_like(ty) {
+                if let AngleBracketed(args) = &segment.arguments {
+                    return args.args.iter().any(|arg| matches!(arg, GenericArgument::Lifetime(lifetime) if lifetime.ident != "static"));
+                }
+            }
+        }
+        false
+    }
+
     pub fn unwrapped_type(ty: &Type) -> Type {
         Self::remove_from_type(ty, "Option")
     }
@@ -54,11 +106,11 @@ impl TypeParser {
         Self::remove_from_type(&Self::remove_from_type(ty, "Option"), "Box")
     }
     
-    pub fn ty_from_vec(ty: &Type) -> Type {
-        Self::remove_from_type(ty, "Vec")
-    }
-
     fn remove_from_type(ty: &Type, remove: &str) -> Type {
+        // Box<[T]> is a collection on its own, the slice can't be stored unboxed
+        if remove == "Box" && Self::is_boxed_slice(ty) {
+            return Self::with_tuborfish(ty);
+        }
         if let Some(segment)  = Self::first_path_segment(ty) {
             if segment.ident.to_string() == remove {
                 if let Some(inner_type) = Self::type_from_segment_args(&segment) {
@@ -136,6 +188,25 @@ fn test_turbofish() {
     assert_eq!(quote::quote! { #result }.to_string(), "i32");
 }
 
+#[test]
+fn test_collection_item() {
+    let ty = syn::parse_quote! { std::collections::HashSet<String> };
+    let result = TypeParser::collection_item(&ty).unwrap();
+    assert_eq!(quote::quote! { #result }.to_string(), "String");
+
+    let ty = syn::parse_quote! { [u8; 4] };
+    let result = TypeParser::collection_item(&ty).unwrap();
+    assert_eq!(quote::quote! { #result }.to_string(), "u8");
+
+    let ty = syn::parse_quote! { Box<[i32]> };
+    let result = TypeParser::collection_item(&ty).unwrap();
+    assert_eq!(quote::quote! { #result }.to_string(), "i32");
+    assert!(TypeParser::type_path_idents(&ty).is_empty());
+
+    let ty = syn::parse_quote! { Box<i32> };
+    assert!(TypeParser::collection_item(&ty).is_none());
+}
+
 #[test]
 fn test_unbox() {
     let ty = syn::parse_quote! { Box<Vec<i32>> };
@@ -153,4 +224,8 @@ fn test_unbox() {
     let ty = syn::parse_quote! { Option<Box<i32>> };
     let result = TypeParser::unbox_and_unwrap_type(&ty);
     assert_eq!(quote::quote! { #result }.to_string(), "i32");
+
+    let ty = syn::parse_quote! { Option<Box<[i32]>> };
+    let result = TypeParser::unbox_and_unwrap_type(&ty);
+    assert_eq!(quote::quote! { #result }.to_string(), "Box :: < [i32] >");
 }
@@ -1,25 +1,45 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::DeriveInput;
+use syn::{Data, DeriveInput, LitStr};
+use crate::common::meta::{MetaInfo, MetaName};
+use crate::common::naming::names::XmlNames;
 
 pub struct XmlEnum;
 
 impl XmlEnum {
-    pub fn parse(_: &DeriveInput) -> TokenStream {
+    pub fn parse(_input: &DeriveInput) -> TokenStream {
         quote!{
-            loop {
-                match reader.read_event() {
-                    Err(error) =>  { return Err(PError::new(&format!("Error at position {}: {:?}", reader.buffer_position(), error))) },
-                    Ok(::xavier::quick_xml::events::Event::Eof) => { },
-                    Ok(::xavier::quick_xml::events::Event::Start(_)) => {},
-                    Ok(::xavier::quick_xml::events::Event::End(_)) => {},
-                    Ok(::xavier::quick_xml::events::Event::Empty(_)) => {},
-                    Ok(::xavier::quick_xml::events::Event::Comment(_)) => {},
-                    Ok(::xavier::quick_xml::events::Event::Text(event)) => { return Ok(Some(String::from_utf8(event.to_vec())?.parse()?)); },
-                    Ok(::xavier::quick_xml::events::Event::CData(event)) => { return Ok(Some(String::from_utf8(event.to_vec())?.parse()?)); },
-                    Ok(::xavier::quick_xml::events::Event::Decl(_)) => {},
-                    Ok(::xavier::quick_xml::events::Event::PI(_)) => {},
-                    Ok(::xavier::quick_xml::events::Event::DocType(_)) => {},
+            let xa_text = xavier::deserialize::primitives::read_text(&mut reader)?.unwrap_or_default();
+            Ok(Some(<Self as xavier::deserialize::text::FromXmlText>::from_xml_text(&xa_text)?))
+        }
+    }
+
+    /* Variant names and aliases, shared by element text, attributes, value fields and map keys.
+       FromStr stays free for the user's own impl */
+    pub fn from_xml_text(input: &DeriveInput) -> TokenStream {
+        let variants = match XmlNames::unit_variants(input) {
+            Ok(variants) => variants,
+            Err(error) => return error.to_compile_error(),
+        };
+        let (idents, names): (Vec<_>, Vec<_>) = variants.into_iter().unzip();
+        let aliases: Vec<Vec<LitStr>> = match &input.data {
+            Data::Enum(data) => data.variants.iter()
+                .map(|variant| XmlNames::aliases(MetaInfo::from_name(&variant.attrs, MetaName::XML).as_ref()))
+                .collect(),
+            _ => vec![],
+        };
+        let expected = names.iter().map(|name| name.value()).collect::<Vec<_>>().join(", ");
+        let object_name = &input.ident;
+        let enum_name = object_name.to_string();
+        let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+        quote!{
+            impl #impl_generics xavier::deserialize::text::FromXmlText for #object_name #ty_generics #where_clause {
+                fn from_xml_text(xa_text: &str) -> Result<Self, xavier::PError> {
+                    match xa_text.trim() {
+                        #(#names #(| #aliases)* => Ok(Self::#idents),)*
+                        xa_value => Err(xavier::PError::new(&format!("Invalid value '{}' for {}, expected one of: {}", xa_value, #enum_name, #expected)))
+                    }
                 }
             }
         }
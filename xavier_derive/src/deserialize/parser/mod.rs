@@ -1,6 +1,9 @@
 pub  mod streams;
 pub mod complex;
 pub mod enums;
+pub mod choice;
+pub mod tagged;
 pub mod empty;
 pub mod simple;
 
+pub mod text;
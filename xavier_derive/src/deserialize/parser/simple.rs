@@ -1,27 +1,20 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::DeriveInput;
+use syn::{Data, DeriveInput};
+use crate::deserialize::parser::text::XmlText;
 
 pub struct XmlSimpleTag;
 
 impl XmlSimpleTag {
 
-    pub fn parse(_: &DeriveInput) -> TokenStream {
+    pub fn parse(input: &DeriveInput) -> TokenStream {
+        let Data::Struct(data) = &input.data else { return quote! {} };
+        let Some(field) = data.fields.iter().next() else { return quote! {} };
+        let value = XmlText::parse(&field.ty, quote! { &xa_text });
         quote!{
-            loop {
-                match reader.read_event() {
-                    Err(error) =>  { return Err(PError::new(&format!("Error at position {}: {:?}", reader.buffer_position(), error))) },
-                    Ok(::xavier::quick_xml::events::Event::Eof) => { },
-                    Ok(::xavier::quick_xml::events::Event::Start(_)) => {},
-                    Ok(::xavier::quick_xml::events::Event::End(_)) => {},
-                    Ok(::xavier::quick_xml::events::Event::Empty(_)) => {},
-                    Ok(::xavier::quick_xml::events::Event::Comment(_)) => {},
-                    Ok(::xavier::quick_xml::events::Event::Text(event)) => { return Ok(Some(Self(String::from_utf8(event.to_vec())?.parse()?))); },
-                    Ok(::xavier::quick_xml::events::Event::CData(event)) => { return Ok(Some(Self(String::from_utf8(event.to_vec())?.parse()?))); },
-                    Ok(::xavier::quick_xml::events::Event::Decl(_)) => {},
-                    Ok(::xavier::quick_xml::events::Event::PI(_)) => {},
-                    Ok(::xavier::quick_xml::events::Event::DocType(_)) => {},
-                }
+            match xavier::deserialize::primitives::read_text(&mut reader)? {
+                Some(xa_text) if !xa_text.is_empty() => Ok(Some(Self(#value))),
+                _ => Ok(None),
             }
         }
     }
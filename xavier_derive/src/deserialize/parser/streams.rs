@@ -4,12 +4,14 @@ use proc_macro2::TokenStream;
 use crate::deserialize::parser::complex::stream::XmlComplex;
 use crate::deserialize::parser::empty::XmlEmptyTag;
 use crate::deserialize::parser::enums::XmlEnum;
+use crate::deserialize::parser::choice::XmlChoice;
+use crate::deserialize::parser::tagged::XmlTagged;
 use crate::deserialize::parser::simple::XmlSimpleTag;
 
 pub struct XmlDeStream;
 
 pub enum DeStreamType {
-    Complex, Simple, Empty, Enum
+    Complex, Simple, Empty, Enum, Choice, Tagged
 }
 
 impl XmlDeStream {
@@ -18,7 +20,9 @@ impl XmlDeStream {
             DeStreamType::Complex => XmlComplex::parse(input),
             DeStreamType::Simple => XmlSimpleTag::parse(input),
             DeStreamType::Empty => XmlEmptyTag::parse(input),
-            DeStreamType::Enum => XmlEnum::parse(input)
+            DeStreamType::Enum => XmlEnum::parse(input),
+            DeStreamType::Choice => XmlChoice::parse(input),
+            DeStreamType::Tagged => XmlTagged::parse(input)
         }
     }
 }
@@ -0,0 +1,114 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{DeriveInput, Error, Fields, LitStr};
+use crate::common::meta::{MetaInfo, MetaName};
+use crate::common::naming::names::XmlNames;
+use crate::deserialize::parser::choice::XmlChoice;
+
+/* Enum read from a single element, the tag_attribute (ex. xsi:type) selects the variant */
+pub struct XmlTagged;
+
+impl XmlTagged {
+    pub fn parse(input: &DeriveInput) -> TokenStream {
+        let obj_meta_info = MetaInfo::from_name(&input.attrs, MetaName::XML);
+        let enum_name = input.ident.to_string();
+        let tag_attribute = obj_meta_info.as_ref().map(|meta| meta.get_or("tag_attribute", "type".to_string())).unwrap_or_default();
+        let missing = LitStr::new(&format!("Attribute '{}' not found for {}", tag_attribute, enum_name), Span::call_site());
+
+        let syn::Data::Enum(data) = &input.data else { return quote! {} };
+        let mut names = vec![];
+        let mut variants = vec![];
+        let mut element_name = quote! {};
+        for variant in &data.variants {
+            let ident = &variant.ident;
+            let variant_meta = MetaInfo::from_name(&variant.attrs, MetaName::XML);
+            let name = XmlNames::variant(ident, obj_meta_info.as_ref(), variant_meta.as_ref());
+            let aliases = XmlNames::aliases(variant_meta.as_ref());
+            let not_found = LitStr::new(&format!("Value not found for '{}'", name.value()), Span::call_site());
+
+            let value = match &variant.fields {
+                Fields::Unit => quote! {{
+                    reader.read_to_end_into(event.name(), &mut Vec::new())?;
+                    Self::#ident
+                }},
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                    let ty = &fields.unnamed[0].ty;
+                    quote! {
+                        match <#ty>::from_xml(&mut reader, Some(event))? {
+                            Some(xa_value) => Self::#ident(xa_value),
+                            None => return Err(PError::new(#not_found)),
+                        }
+                    }
+                },
+                Fields::Named(fields) => {
+                    // Struct variants end with the element they started on, whatever its name
+                    element_name = quote! { let xa_element_name = String::from_utf8(event.name().0.to_vec())?; };
+                    XmlChoice::struct_variant(ident, fields, obj_meta_info.as_ref(), quote! { xa_element_name }, &[], &not_found)
+                },
+                Fields::Unnamed(_) => {
+                    return Error::new_spanned(variant, "Tuple variants must have a single field.").to_compile_error();
+                }
+            };
+            names.push(name.value());
+            variants.push(quote! {
+                if xa_type == #name #(|| xa_type == #aliases)* {
+                    return Ok(Some(#value));
+                }
+            });
+        }
+        let expected = names.join(", ");
+
+        quote! {
+            let xa_dispatch = |mut reader: &mut ::xavier::quick_xml::Reader<XaRead>, event: &::xavier::quick_xml::events::BytesStart| -> Result<Option<Self>, PError> {
+                let xa_type = match event.try_get_attribute(#tag_attribute)? {
+                    Some(xa_attribute) => xavier::deserialize::text::decode_text(&xa_attribute.value)?,
+                    None => return Err(PError::new(#missing)),
+                };
+                #element_name
+                #(#variants)*
+                Err(PError::new(&format!("Unknown {} '{}' for {}, expected one of: {}", #tag_attribute, xa_type, #enum_name, #expected)))
+            };
+
+            // The start event is either the typed element itself or a wrapper holding it
+            let mut xa_wrapper: Option<Vec<u8>> = None;
+            if let Some(event) = start_event {
+                if event.try_get_attribute(#tag_attribute)?.is_some() {
+                    return xa_dispatch(&mut *reader, event);
+                }
+                xa_wrapper = Some(event.name().0.to_vec());
+            }
+
+            let mut xa_result: Option<Self> = None;
+            let mut xa_buffer = Vec::new();
+            loop {
+                xa_buffer.clear();
+                match reader.read_event_into(&mut xa_buffer) {
+                    Err(error) =>  { return Err(PError::new(&format!("Error at position {}: {:?}", reader.buffer_position(), error))) },
+                    Ok(::xavier::quick_xml::events::Event::Start(event)) => {
+                        if xa_result.is_some() {
+                            reader.read_to_end_into(event.name(), &mut Vec::new())?;
+                            continue;
+                        }
+                        xa_result = xa_dispatch(&mut *reader, &event)?;
+                        if xa_wrapper.is_none() {
+                            return Ok(xa_result);
+                        }
+                    },
+                    Ok(::xavier::quick_xml::events::Event::End(event)) => {
+                        if xa_wrapper.as_deref() == Some(event.name().0) {
+                            return Ok(xa_result);
+                        }
+                    },
+                    Ok(::xavier::quick_xml::events::Event::Eof) => { return Ok(xa_result) },
+                    Ok(::xavier::quick_xml::events::Event::Empty(_)) => {},
+                    Ok(::xavier::quick_xml::events::Event::Text(_)) => {},
+                    Ok(::xavier::quick_xml::events::Event::CData(_)) => {},
+                    Ok(::xavier::quick_xml::events::Event::Decl(_)) => {},
+                    Ok(::xavier::quick_xml::events::Event::PI(_)) => {},
+                    Ok(::xavier::quick_xml::events::Event::DocType(_)) => {},
+                    Ok(::xavier::quick_xml::events::Event::Comment(_)) => {}
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+pub struct XmlText;
+
+impl XmlText {
+    /* Text to a value of type ty, derived text conversions (unit enums) first and FromStr for every other type */
+    pub fn parse(ty: &impl ToTokens, text: TokenStream) -> TokenStream {
+        quote! {
+            {
+                use xavier::deserialize::text::{ParseStrText as _, ParseXmlText as _};
+                (&&xavier::deserialize::text::TextParser::<#ty>(std::marker::PhantomData)).parse_text(#text)?
+            }
+        }
+    }
+}
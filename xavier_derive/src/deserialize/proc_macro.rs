@@ -3,29 +3,46 @@ use proc_macro::TokenStream;
 use proc_macro2::Span;
 use quote::quote;
 use syn::Data::{Enum, Struct, Union};
-use syn::{LitStr, parse_macro_input};
+use syn::{GenericParam, Generics, Lifetime, LifetimeParam, LitStr, parse_macro_input};
 use syn::{DataEnum, DataUnion, DeriveInput, Error, Fields, FieldsNamed, FieldsUnnamed};
 use crate::common::meta::{MetaInfo, MetaName};
 use crate::common::naming::names::XmlNames;
 
+use crate::deserialize::parser::choice::XmlChoice;
+use crate::deserialize::parser::enums::XmlEnum;
 use crate::deserialize::parser::streams::{DeStreamType, XmlDeStream};
 
 pub fn impl_xml_deserializable(input: TokenStream) -> TokenStream {
 
     let input  = parse_macro_input!(input as DeriveInput);
-    let (impl_generics, ty_generics, where_clause) = &input.generics.split_for_impl();
+    let generics = input_generics(&input.generics);
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, where_clause) = &input.generics.split_for_impl();
     let object_name = &input.ident;
 
     let obj_meta_info = MetaInfo::from_name(&input.attrs, MetaName::XML);
     let xml_tag_name = LitStr::new(&XmlNames::root(&input, obj_meta_info.as_ref()), Span::call_site());
+    let aliases = XmlNames::aliases(obj_meta_info.as_ref());
 
+    let mut mixed_items = quote! {};
+    let mut text_impl = quote! {};
     let xml_code = match &input.data {
         Struct(obj) => match &obj.fields {
             Fields::Named(FieldsNamed { .. }) => { XmlDeStream::stream(&input, DeStreamType::Complex) },
             Fields::Unnamed(FieldsUnnamed { .. }) => { XmlDeStream::stream(&input, DeStreamType::Simple) }
             Fields::Unit => { XmlDeStream::stream(&input, DeStreamType::Empty) }
         },
-        Enum(DataEnum { .. }) => { XmlDeStream::stream(&input, DeStreamType::Enum) },
+        Enum(DataEnum { variants, .. }) => {
+            if obj_meta_info.as_ref().map(|meta| meta.contains("tag_attribute")).unwrap_or(false) {
+                XmlDeStream::stream(&input, DeStreamType::Tagged)
+            } else if variants.iter().all(|variant| matches!(variant.fields, Fields::Unit)) {
+                text_impl = XmlEnum::from_xml_text(&input);
+                XmlDeStream::stream(&input, DeStreamType::Enum)
+            } else {
+                mixed_items = XmlChoice::mixed_items(&input);
+                XmlDeStream::stream(&input, DeStreamType::Choice)
+            }
+        },
         Union(DataUnion { .. }) => {
             let message = "Proc macro 'xml deserialize' does not support this type of object config.";
             return Error::new_spanned(object_name, message).to_compile_error().into();} 
@@ -33,15 +50,30 @@ pub fn impl_xml_deserializable(input: TokenStream) -> TokenStream {
 
     let expanded = quote! {
 
-        impl #impl_generics xavier::deserialize::macro_trait::XmlDeserializable for #object_name #ty_generics #where_clause {
-            fn from_xml(mut reader: &mut ::xavier::quick_xml::Reader<&[u8]>, start_event: Option<&::xavier::quick_xml::events::BytesStart>) -> Result<Option<Self>, xavier::PError> {
+        impl #impl_generics xavier::deserialize::macro_trait::XmlDeserializable<'xa_de> for #object_name #ty_generics #where_clause {
+            fn from_xml<XaRead: xavier::deserialize::source::XmlSource<'xa_de>>(mut reader: &mut ::xavier::quick_xml::Reader<XaRead>, start_event: Option<&::xavier::quick_xml::events::BytesStart>) -> Result<Option<Self>, xavier::PError> {
                 #xml_code
             }
             fn inner_name() -> Option<String> {
                 Some(#xml_tag_name.to_string())
             }
+            fn accepts_name(name: &str) -> bool {
+                name == #xml_tag_name #(|| name == #aliases)*
+            }
+            #mixed_items
         }
+
+        #text_impl
     };
 
     TokenStream::from(expanded)
 }
+
+/* Adds the input lifetime, it outlives every lifetime of the type so borrowed fields can point into it */
+fn input_generics(generics: &Generics) -> Generics {
+    let mut input_lifetime = LifetimeParam::new(Lifetime::new("'xa_de", Span::call_site()));
+    input_lifetime.bounds.extend(generics.lifetimes().map(|param| param.lifetime.clone()));
+    let mut generics = generics.clone();
+    generics.params.insert(0, GenericParam::Lifetime(input_lifetime));
+    generics
+}
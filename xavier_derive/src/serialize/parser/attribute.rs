@@ -1,4 +1,4 @@
-use syn::{LitStr, Type};
+use syn::{LitStr, Path, Type};
 use proc_macro2::{ Ident, TokenStream };
 use quote::{ quote, ToTokens };
 use crate::common::meta::MetaInfo;
@@ -7,38 +7,55 @@ use crate::serialize::parser::extension::XmlExtension;
 use crate::serialize::parser::types::is_outer_option;
 
 pub struct XmlElementAttr {
-    pub field: Ident,
+    pub value: TokenStream,
     pub name: LitStr,
-    pub ty: Type
+    pub ty: Type,
+    pub with: Option<Path>,
+    pub skip_if: Option<Path>,
+    /* #[xml(other_attributes)], a list of (name, value) pairs */
+    pub other: bool,
 }
 
 impl ToTokens for XmlElementAttr {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let field = &self.field;
+        let value = &self.value;
         let name = &self.name;
-        let attr_tokens = if is_outer_option(&self.ty) {
-            quote! {
-                if self.#field.is_none() {
-                    "".to_string()
-                } else {
-                    format!(" {}=\"{}\"", #name, xavier::serialize::encode::escape_xml(&self.#field.to_xml(false)))
+        let attr_tokens = match (is_outer_option(&self.ty), &self.with) {
+            _ if self.other => quote! {
+                for (name, value) in #value.iter() {
+                    writer.write_attribute(name, value)?;
                 }
-            }
-        } else {
-            quote! {
-                format!(" {}=\"{}\"", #name, xavier::serialize::encode::escape_xml(&self.#field.to_xml(false)))
-            }
+            },
+            (true, Some(with)) => quote! {
+                if let Some(value) = &#value {
+                    writer.write_attribute(#name, &#with(value))?;
+                }
+            },
+            (true, None) => quote! {
+                if let Some(value) = &#value {
+                    writer.write_attribute(#name, value)?;
+                }
+            },
+            (false, Some(with)) => quote! {
+                writer.write_attribute(#name, &#with(&#value))?;
+            },
+            (false, None) => quote! {
+                writer.write_attribute(#name, &#value)?;
+            },
         };
-        tokens.extend(attr_tokens);
+        match &self.skip_if {
+            Some(skip_if) => tokens.extend(quote! { if !#skip_if(&#value) { #attr_tokens } }),
+            None => tokens.extend(attr_tokens),
+        }
     }
 }
 
 impl XmlElementAttr {
-    pub fn parse(field: Ident, ty: Type, obj_meta: Option<&MetaInfo>, meta: Option<&MetaInfo>, _: XmlExtension) -> Option<XmlElementAttr> {
+    pub fn parse(field: Ident, value: TokenStream, ty: Type, obj_meta: Option<&MetaInfo>, meta: Option<&MetaInfo>, _: XmlExtension) -> Option<XmlElementAttr> {
         meta.and_then(|meta| {
-            if meta.contains("attribute") {
+            if meta.contains("attribute") || meta.contains("other_attributes") {
                 let name = XmlNames::attribute(&field, obj_meta, &meta);
-                Some(XmlElementAttr { field, name, ty })
+                Some(XmlElementAttr { value, name, ty, with: meta.with_fn("serialize"), skip_if: meta.path("skip_serializing_if"), other: meta.contains("other_attributes") })
             } else {
                 None
             }
@@ -0,0 +1,84 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{DeriveInput, Error, Fields, FieldsNamed, LitStr};
+use crate::common::meta::{MetaInfo, MetaName};
+use crate::common::naming::names::XmlNames;
+use crate::serialize::parser::complex::XmlComplexTag;
+use crate::serialize::parser::declaration::XmlDeclaration;
+use crate::serialize::parser::dtd::XmlDTD;
+use crate::serialize::parser::element::XmlElementDef;
+use crate::serialize::parser::instructions::XmlPI;
+
+/* Enum with data carrying variants, every variant is written as its own element */
+pub(crate) struct XmlChoice;
+
+impl XmlChoice {
+    pub fn parse(input: &DeriveInput) -> TokenStream {
+        let obj_meta_info = MetaInfo::from_name(&input.attrs, MetaName::XML);
+        let tag = LitStr::new(&XmlNames::root(input, obj_meta_info.as_ref()), Span::call_site());
+        let dtd = XmlDTD::parse(input, &tag);
+        let pi = XmlPI::parse(input);
+        let declaration = XmlDeclaration::parse(input, &tag);
+
+        let syn::Data::Enum(data) = &input.data else { return quote! {} };
+        let mut arms = vec![];
+        for variant in &data.variants {
+            let ident = &variant.ident;
+            let variant_meta = MetaInfo::from_name(&variant.attrs, MetaName::XML);
+            let name = XmlNames::variant(ident, obj_meta_info.as_ref(), variant_meta.as_ref());
+            let arm = match &variant.fields {
+                Fields::Unit => quote! {
+                    Self::#ident => {
+                        writer.start_tag(#name)?;
+                        writer.end_start_tag()?;
+                        writer.end_tag(#name)?;
+                    }
+                },
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                    let is_text = variant_meta.as_ref().is_some_and(|meta| meta.contains("text"));
+                    // Text of mixed content and tree variants are written as they are
+                    if is_text || variant_meta.map(|meta| meta.contains("tree")).unwrap_or(false) {
+                        quote! { Self::#ident(xa_value) => { xa_value.write_xml(writer, false)?; } }
+                    } else {
+                        quote! {
+                            Self::#ident(xa_value) => {
+                                writer.start_tag(#name)?;
+                                writer.end_start_tag()?;
+                                xa_value.write_xml(writer, false)?;
+                                writer.end_tag(#name)?;
+                            }
+                        }
+                    }
+                },
+                Fields::Named(fields) => XmlChoice::struct_variant(ident, fields, obj_meta_info.as_ref(), &name, quote! {}),
+                Fields::Unnamed(_) => {
+                    return Error::new_spanned(variant, "Tuple variants must have a single field.").to_compile_error();
+                }
+            };
+            arms.push(arm);
+        }
+
+        quote! {
+            #declaration
+            #pi
+            #dtd
+            match self {
+                #(#arms)*
+            }
+        }
+    }
+
+    /* Struct variants are written like a struct named after the variant, fields are bound by the match */
+    pub fn struct_variant(ident: &Ident, fields: &FieldsNamed, obj_meta: Option<&MetaInfo>, tag: &LitStr, extra_attributes: TokenStream) -> TokenStream {
+        let bindings = fields.named.iter().filter_map(|field| field.ident.clone()).map(|field| {
+            let binding = format_ident!("xa_{}", field);
+            quote! { #field: #binding }
+        });
+        let elements = XmlElementDef::from_named(fields, obj_meta, |field| {
+            let binding = format_ident!("xa_{}", field);
+            quote! { (*#binding) }
+        });
+        let element = XmlComplexTag::element(tag, elements, false, extra_attributes);
+        quote! { Self::#ident { #(#bindings),* } => { #element } }
+    }
+}
@@ -21,53 +21,58 @@ impl XmlComplexTag {
         let declaration = XmlDeclaration::parse(&input, &tag);
 
         if let Some(elements) = elements {
-            let attributes = elements.attributes;
-            let children = elements.tags;
-            let flatten = LitBool::new(obj_meta_info.unwrap_or(MetaInfo::empty()).contains("flatten"), Span::call_site());
-
-            let namespace_tokens = if let Some(namespace) = elements.namespace {
-                quote! { let namespace = &self.#namespace; }
-            } else {
-                quote! { let namespace = ""; }
-            };
-
+            let flatten = obj_meta_info.unwrap_or(MetaInfo::empty()).contains("flatten");
+            let element = XmlComplexTag::element(&tag, elements, flatten, quote! {});
+            quote! {
+                #declaration
+                #pi
+                #dtd
+                #element
+            }
+        } else {
             quote! {
-                #namespace_tokens
-
-                let mut xml = String::new();
                 let tag = #tag;
+                #declaration
+                #pi
+                #dtd
+                writer.start_tag(tag)?;
+                writer.end_start_tag()?;
+                writer.end_tag(tag)?;
+            }
+        }
+    }
 
-                xml.push_str(&#declaration);
-                xml.push_str(&#pi);
-                xml.push_str(&#dtd);
+    /* Start tag with namespace and attributes, the children and the end tag */
+    pub fn element(tag: &LitStr, elements: XmlElementDef, flatten: bool, extra_attributes: TokenStream) -> TokenStream {
+        let attributes = elements.attributes;
+        let children = elements.tags;
+        let flatten = LitBool::new(flatten, Span::call_site());
 
-                let mut attributes = String::new();
-                #(attributes.push_str(&#attributes);)*
+        let namespace_tokens = if let Some(namespace) = elements.namespace {
+            quote! { let namespace = &#namespace; }
+        } else {
+            quote! { let namespace = ""; }
+        };
 
-                if !#flatten {
-                    xml.push_str("<");
-                    xml.push_str(&tag);
-                    if !namespace.is_empty() {
-                        xml.push_str(&namespace);
-                    }
-                    if !attributes.is_empty() {
-                        xml.push_str(&attributes);
-                    }
-                    xml.push_str(">");
-                }
+        quote! {
+            #namespace_tokens
 
-                let mut children = String::new();
-                #(children.push_str(&#children);)*
-                xml.push_str(&children);
+            let tag = #tag;
 
-                if !#flatten {
-                   xml.push_str(&format!("</{}>", tag));
+            if !#flatten {
+                writer.start_tag(tag)?;
+                if !namespace.is_empty() {
+                    writer.write_raw(namespace)?;
                 }
+                #extra_attributes
+                #(#attributes)*
+                writer.end_start_tag()?;
             }
-        } else {
-            quote! {
-                let tag = #tag;
-                let xml = format!("{}{}{}<{}></{}>", #declaration, #pi, #dtd, tag, tag).to_string();
+
+            #(#children)*
+
+            if !#flatten {
+                writer.end_tag(tag)?;
             }
         }
     }
@@ -10,9 +10,7 @@ impl XmlDeclaration {
     pub fn parse(input: &DeriveInput, _: &LitStr) -> TokenStream {
         let xml_declaration = XmlDeclaration::xml_declaration(input);
         quote! {
-            {
-                if root { #xml_declaration.to_string() } else { "".to_string() }
-            }
+            if root { writer.write_markup(#xml_declaration)?; }
         }
     }
 
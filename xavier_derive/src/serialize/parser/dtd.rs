@@ -10,9 +10,7 @@ impl XmlDTD {
     pub fn parse(input: &DeriveInput, tag: &LitStr) -> TokenStream {
         let dtd_def = XmlDTD::dtd_def(input, tag);
         quote! {
-            {
-                if root { #dtd_def.to_string() } else { "".to_string() }
-            }
+            if root { writer.write_markup(#dtd_def)?; }
         }
     }
 
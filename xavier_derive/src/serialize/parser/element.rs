@@ -1,6 +1,7 @@
 use syn::Data::Struct;
 use syn::{DeriveInput, Fields, FieldsNamed, Type};
-use proc_macro2::Ident;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
 use crate::common::meta::{ MetaInfo, MetaName };
 use crate::serialize::parser::attribute::XmlElementAttr;
 use crate::serialize::parser::namespace::XmlNamespace;
@@ -8,7 +9,7 @@ use crate::serialize::parser::tag::XmlTagElement;
 use crate::serialize::parser::extension::XmlExtension;
 
 pub struct XmlElementDef {
-    pub namespace: Option<Ident>,
+    pub namespace: Option<TokenStream>,
     pub tags: Vec<XmlTagElement>,
     pub attributes: Vec<XmlElementAttr>,
 }
@@ -17,28 +18,42 @@ impl XmlElementDef {
     pub fn parse(input: &DeriveInput, obj_meta: Option<&MetaInfo>) -> Option<XmlElementDef> {
         if let Struct(struct_item) = &input.data {
             if let Fields::Named(fields) = &struct_item.fields {
-                return Some(XmlElementDef {
-                    namespace: XmlElementDef::from_fields_first(&fields, obj_meta, XmlNamespace::parse),
-                    tags: XmlElementDef::from_fields(&fields, obj_meta, XmlTagElement::parse),
-                    attributes: XmlElementDef::from_fields(&fields, obj_meta, XmlElementAttr::parse)
-                })
+                return Some(XmlElementDef::from_named(fields, obj_meta, |field| quote! { self.#field }))
             }
         }
         None
     }
 
-    fn from_fields<T, P>(fields: &FieldsNamed, obj_meta: Option<&MetaInfo>, parse: P) -> Vec<T>
-        where P: Fn(Ident, Type, Option<&MetaInfo>, Option<&MetaInfo>, XmlExtension) -> Option<T> {
+    /* The accessor turns a field name into the expression that reads it (self.field, a match binding...) */
+    pub fn from_named<A>(fields: &FieldsNamed, obj_meta: Option<&MetaInfo>, access: A) -> XmlElementDef
+        where A: Fn(&Ident) -> TokenStream {
+        XmlElementDef {
+            namespace: XmlElementDef::from_fields_first(fields, obj_meta, &access, XmlNamespace::parse),
+            tags: XmlElementDef::from_fields(fields, obj_meta, &access, XmlTagElement::parse),
+            attributes: XmlElementDef::from_fields(fields, obj_meta, &access, XmlElementAttr::parse)
+        }
+    }
+
+    fn from_fields<T, A, P>(fields: &FieldsNamed, obj_meta: Option<&MetaInfo>, access: &A, parse: P) -> Vec<T>
+        where A: Fn(&Ident) -> TokenStream,
+              P: Fn(Ident, TokenStream, Type, Option<&MetaInfo>, Option<&MetaInfo>, XmlExtension) -> Option<T> {
 
         fields.named.iter().filter_map(|field| {
-            parse(field.ident.clone()?, field.ty.clone(), obj_meta, MetaInfo::from_name(&field.attrs, MetaName::XML).as_ref(), XmlExtension::from_field(field))
+            let ident = field.ident.clone()?;
+            let meta = MetaInfo::from_name(&field.attrs, MetaName::XML);
+            if meta.as_ref().is_some_and(|meta| meta.contains("skip") || meta.contains("skip_serializing")) {
+                return None;
+            }
+            let value = access(&ident);
+            parse(ident, value, field.ty.clone(), obj_meta, meta.as_ref(), XmlExtension::from_field(field))
         }).collect()
     }
 
-    fn from_fields_first<T, P>(fields: &FieldsNamed, obj_meta: Option<&MetaInfo>, parse: P) -> Option<T>
-        where P: Fn(Ident, Type, Option<&MetaInfo>, Option<&MetaInfo>, XmlExtension) -> Option<T>, T: Clone {
+    fn from_fields_first<T, A, P>(fields: &FieldsNamed, obj_meta: Option<&MetaInfo>, access: &A, parse: P) -> Option<T>
+        where A: Fn(&Ident) -> TokenStream,
+              P: Fn(Ident, TokenStream, Type, Option<&MetaInfo>, Option<&MetaInfo>, XmlExtension) -> Option<T>, T: Clone {
 
-        let result = Self::from_fields(fields, obj_meta, parse);
+        let result = Self::from_fields(fields, obj_meta, access, parse);
         if let Some(first) = result.first().cloned() {
             if result.len() > 1 {
 
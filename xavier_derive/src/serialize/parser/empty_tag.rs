@@ -11,7 +11,8 @@ impl XmlEmptyTag {
         let meta_info = MetaInfo::from_name(&input.attrs, MetaName::XML);
         let tag = XmlNames::root(&input, meta_info.as_ref());
         quote! {
-            let xml = format!("<{}/>", #tag).to_string();
+            writer.start_tag(#tag)?;
+            writer.end_empty_tag()?;
         }
     }
-}
\ No newline at end of file
+}
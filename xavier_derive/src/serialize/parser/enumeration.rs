@@ -1,10 +1,34 @@
 use quote::quote;
 use syn::DeriveInput;
+use crate::common::naming::names::XmlNames;
 
 pub(crate) struct XmlEnumValue;
 
 impl XmlEnumValue {
-    pub fn parse(_: &DeriveInput) -> proc_macro2::TokenStream {
-        quote! { let xml = self.to_string(); }
+    pub fn parse(_input: &DeriveInput) -> proc_macro2::TokenStream {
+        quote! {
+            writer.write_text(&xavier::serialize::text::ToXmlText::to_xml_text(self))?;
+        }
+    }
+
+    /* Text with the variant names, shared by element text, attributes and map keys.
+       Display stays free for the user's own impl */
+    pub fn to_xml_text(input: &DeriveInput) -> proc_macro2::TokenStream {
+        let variants = match XmlNames::unit_variants(input) {
+            Ok(variants) => variants,
+            Err(error) => return error.to_compile_error(),
+        };
+        let (idents, names): (Vec<_>, Vec<_>) = variants.into_iter().unzip();
+        let object_name = &input.ident;
+        let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+        quote! {
+            impl #impl_generics xavier::serialize::text::ToXmlText for #object_name #ty_generics #where_clause {
+                fn to_xml_text(&self) -> std::borrow::Cow<'_, str> {
+                    match *self {
+                        #(Self::#idents => std::borrow::Cow::Borrowed(#names),)*
+                    }
+                }
+            }
+        }
     }
 }
@@ -11,11 +11,7 @@ impl ToTokens for XmlExtension {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let extensions = &self.0;
         tokens.extend(quote! {
-             {
-                 let mut extensions = String::new();
-                 #(extensions.push_str(&#extensions);)*
-                 extensions
-             }
+             #(writer.write_markup(#extensions)?;)*
         });
     }
 }
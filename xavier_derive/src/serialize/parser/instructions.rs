@@ -9,10 +9,7 @@ impl XmlPI {
     pub fn parse(input: &DeriveInput) -> TokenStream {
         let pis_def = XmlPI::literals_from_attrs(MetaInfo::vec_attr_by_name(&input.attrs, MetaName::PI));
         quote! {
-            {   let mut pi = String::new();
-                #(pi.push_str(&#pis_def);)*
-                pi
-            }
+            #(writer.write_markup(#pis_def)?;)*
         }
     }
 
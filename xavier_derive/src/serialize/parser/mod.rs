@@ -1,7 +1,9 @@
 mod complex;
 mod empty_tag;
 mod simple;
-mod enumeration;
+pub mod enumeration;
+mod choice;
+mod tagged;
 mod element;
 mod attribute;
 mod declaration;
@@ -1,4 +1,4 @@
-use proc_macro2::Ident;
+use proc_macro2::{Ident, TokenStream};
 use syn::Type;
 use crate::common::meta::MetaInfo;
 use crate::serialize::parser::extension::XmlExtension;
@@ -6,10 +6,10 @@ use crate::serialize::parser::extension::XmlExtension;
 pub struct  XmlNamespace;
 
 impl XmlNamespace {
-    pub fn parse(field: Ident, _: Type,  _: Option<&MetaInfo>, meta: Option<&MetaInfo>, _: XmlExtension) -> Option<Ident> {
+    pub fn parse(_: Ident, value: TokenStream, _: Type,  _: Option<&MetaInfo>, meta: Option<&MetaInfo>, _: XmlExtension) -> Option<TokenStream> {
         meta.and_then(|meta| {
             if meta.contains("xmlns") {
-                Some(field)
+                Some(value)
             } else {
                 None
             }
@@ -11,7 +11,10 @@ impl XmlSimpleTag {
         let meta_info = MetaInfo::from_name(&input.attrs, MetaName::XML);
         let tag = XmlNames::root(&input, meta_info.as_ref());
         quote! {
-            let xml = format!("<{}>{}</{}>", #tag, &self.0.to_xml(false), #tag).to_string();
+            writer.start_tag(#tag)?;
+            writer.end_start_tag()?;
+            self.0.write_xml(writer, false)?;
+            writer.end_tag(#tag)?;
         }
     }
-}
\ No newline at end of file
+}
@@ -4,32 +4,34 @@ use crate::serialize::parser::complex::XmlComplexTag;
 use crate::serialize::parser::simple::XmlSimpleTag;
 use crate::serialize::parser::empty_tag::XmlEmptyTag;
 use crate::serialize::parser::enumeration::XmlEnumValue;
+use crate::serialize::parser::choice::XmlChoice;
+use crate::serialize::parser::tagged::XmlTagged;
 use crate::serialize::parser::encoding::XmlEncoding;
 
 pub struct XmlSerStream;
 
 pub enum SerStreamType {
-    Complex, Simple, Empty, Enum
+    Complex, Simple, Empty, Enum, Choice, Tagged
 }
 
 impl XmlSerStream {
     pub(crate) fn stream(input: &DeriveInput, typed: SerStreamType) -> proc_macro2::TokenStream {
-        let mut xml_stream = match typed {
+        let xml_stream = match typed {
             SerStreamType::Complex => XmlComplexTag::parse(input),
             SerStreamType::Simple => XmlSimpleTag::parse(input),
             SerStreamType::Empty => XmlEmptyTag::parse(input),
-            SerStreamType::Enum => XmlEnumValue::parse(input)
+            SerStreamType::Enum => XmlEnumValue::parse(input),
+            SerStreamType::Choice => XmlChoice::parse(input),
+            SerStreamType::Tagged => XmlTagged::parse(input)
         };
 
         let encoding = XmlEncoding::parse(input);
-        let return_tokens = quote! {
-            if #encoding == "UTF-16" {
-                unimplemented!("UTF-16 is not supported yet");
-            } else {
-                xml
+        quote! {
+            if root {
+                writer.set_encoding(#encoding)?;
             }
-        };
-        xml_stream.extend(return_tokens);
-        xml_stream
+            #xml_stream
+            Ok(())
+        }
     }
 }
@@ -1,85 +1,214 @@
 use proc_macro2::{Ident, TokenStream};
 use quote::{quote, ToTokens};
-use syn::{LitStr, Type};
+use syn::{LitStr, Path, Type};
 use crate::common::meta::MetaInfo;
 use crate::common::naming::names::XmlNames;
 use crate::serialize::parser::extension::XmlExtension;
 use crate::serialize::parser::types::is_outer_option;
 
-pub enum XmlTagElement {
-    Complex(Ident, XmlExtension),
-    Simple(Ident, Type, LitStr, XmlExtension),
-    Value(Ident, XmlExtension),
-    Collection(Ident, LitStr, LitStr, XmlExtension), // field, tag_name, inner_name, extension
+pub enum XmlTagKind {
+    Complex,
+    Simple(Type, LitStr),
+    Value,
+    Collection(LitStr, LitStr), // tag_name, inner_name
+    Map(Type, LitStr, LitStr, LitStr), // tag_name, entry_name, key_name
+    /* #[xml(mixed)], the writer is told first so no indentation gets between the items */
+    Mixed,
+}
+
+pub struct XmlTagElement {
+    pub value: TokenStream,
+    pub kind: XmlTagKind,
+    pub extension: XmlExtension,
+    pub raw: bool,
+    /* Any IntoIterator collection marked with #[xml(collection)], written item by item */
+    pub collection: bool,
+    /* #[xml(serialize_with)] function, its result is written instead of the field */
+    pub with: Option<Path>,
+    pub optional: bool,
+    /* #[xml(skip_serializing_if)] predicate, called with a reference to the field */
+    pub skip_if: Option<Path>,
 }
 
 impl ToTokens for XmlTagElement {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-         let tag_tokens = match self {
-            XmlTagElement::Simple(field, ty, name, extensions) => {
+        let field = &self.value;
+        let extensions = &self.extension;
+         let tag_tokens = match &self.kind {
+            XmlTagKind::Simple(ty, name) => {
                 if is_outer_option(&ty) {
+                    let write_value = self.write_value(quote! { (*value) });
                     quote! {
-                        if self.#field.is_none()  {
-                            "".to_string()
-                        } else {
-                            format!("{}<{}>{}</{}>", #extensions, #name, self.#field.to_xml(false), #name)
+                        if let Some(value) = &#field {
+                            #extensions
+                            writer.start_tag(#name)?;
+                            writer.end_start_tag()?;
+                            #write_value
+                            writer.end_tag(#name)?;
                         }
                     }
                 } else {
-                    quote! { format!("{}<{}>{}</{}>", #extensions, #name, self.#field.to_xml(false), #name) }
+                    let write_value = self.write_value(quote! { #field });
+                    quote! {
+                        #extensions
+                        writer.start_tag(#name)?;
+                        writer.end_start_tag()?;
+                        #write_value
+                        writer.end_tag(#name)?;
+                    }
                 }
             },
-            XmlTagElement::Complex(field, extensions) =>  {
+            XmlTagKind::Value if self.with.is_some() && self.optional => {
+                let write_value = self.write_value(quote! { (*value) });
                 quote! {
-                    format!("{}{}", #extensions, self.#field.to_xml(false))
+                    if let Some(value) = &#field {
+                        #extensions
+                        #write_value
+                    }
                 }
             },
-            XmlTagElement::Value(field, extensions) =>  {
+            XmlTagKind::Mixed => {
+                let write_value = self.write_value(quote! { #field });
                 quote! {
-                    format!("{}{}", #extensions, self.#field.to_xml(false))
+                    #extensions
+                    writer.begin_mixed();
+                    #write_value
                 }
             },
-            XmlTagElement::Collection(field, tag_name, inner_name, extensions) => {
+            XmlTagKind::Complex | XmlTagKind::Value =>  {
+                let write_value = self.write_value(quote! { #field });
                 quote! {
-                    {
-                        let mut collection_xml = String::new();
-                        collection_xml.push_str(&format!("<{}>", #tag_name));
-                        for item in &self.#field {
-                            collection_xml.push_str(&format!("<{}>{}</{}>", #inner_name, item.to_xml(false), #inner_name));
+                    #extensions
+                    #write_value
+                }
+            },
+            XmlTagKind::Collection(tag_name, inner_name) => {
+                let write_value = self.write_item(quote! { item });
+                let write_items = if self.collection {
+                    quote! {
+                        for item in &#field {
+                            writer.start_tag(#inner_name)?;
+                            writer.end_start_tag()?;
+                            #write_value
+                            writer.end_tag(#inner_name)?;
+                        }
+                    }
+                } else {
+                    // Known collections go through XmlItems, it keeps deterministic output for unordered ones
+                    quote! {
+                        {
+                            use xavier::serialize::collections::XmlItems as _;
+                            #field.write_items(writer, |writer, item| {
+                                writer.start_tag(#inner_name)?;
+                                writer.end_start_tag()?;
+                                #write_value
+                                writer.end_tag(#inner_name)
+                            })?;
                         }
-                        collection_xml.push_str(&format!("</{}>", #tag_name));
-                        format!("{}{}", #extensions, collection_xml)
+                    }
+                };
+                quote! {
+                    #extensions
+                    writer.start_tag(#tag_name)?;
+                    writer.end_start_tag()?;
+                    #write_items
+                    writer.end_tag(#tag_name)?;
+                }
+            },
+            XmlTagKind::Map(ty, tag_name, entry_name, key_name) => {
+                let write_entries = |value: TokenStream| quote! {
+                    writer.start_tag(#tag_name)?;
+                    writer.end_start_tag()?;
+                    xavier::serialize::map::write_entries(writer, #value, #entry_name, #key_name)?;
+                    writer.end_tag(#tag_name)?;
+                };
+                if is_outer_option(ty) {
+                    let write_entries = write_entries(quote! { value });
+                    quote! {
+                        if let Some(value) = &#field {
+                            #extensions
+                            #write_entries
+                        }
+                    }
+                } else {
+                    let write_entries = write_entries(quote! { &#field });
+                    quote! {
+                        #extensions
+                        #write_entries
                     }
                 }
             }
         };
-        tokens.extend(tag_tokens);
+        match &self.skip_if {
+            Some(skip_if) => tokens.extend(quote! { if !#skip_if(&#field) { #tag_tokens } }),
+            None => tokens.extend(tag_tokens),
+        }
     }
 }
 
 impl XmlTagElement {
-    pub fn parse(field: Ident, ty: Type, obj_meta: Option<&MetaInfo>, meta: Option<&MetaInfo>, extension: XmlExtension) -> Option<XmlTagElement> {
+    pub fn parse(field: Ident, value: TokenStream, ty: Type, obj_meta: Option<&MetaInfo>, meta: Option<&MetaInfo>, extension: XmlExtension) -> Option<XmlTagElement> {
 
         if let Some(meta) = meta {
-            if !meta.contains("attribute") && !meta.contains("xmlns") {
-                return if meta.contains("tree") {
-                    Some(XmlTagElement::Complex(field, extension))
-                } else if meta.contains("flatten") || meta.contains("value") {
-                    Some(XmlTagElement::Value(field, extension))
+            if !meta.contains("attribute") && !meta.contains("xmlns") && !meta.contains("other_attributes") {
+                let raw = meta.contains("raw");
+                let collection = meta.contains("collection");
+                let with = meta.with_fn("serialize");
+                let optional = is_outer_option(&ty);
+                let kind = if meta.contains("flatten") || meta.contains("value") || meta.contains("other_elements") {
+                    XmlTagKind::Value
+                } else if meta.contains("mixed") {
+                    XmlTagKind::Mixed
+                } else if with.is_some() {
+                    let tag_name = XmlNames::tag(&field, obj_meta, Some(meta));
+                    XmlTagKind::Simple(ty, tag_name)
+                } else if meta.contains("tree") {
+                    XmlTagKind::Complex
                 } else if meta.contains("inner") {
                     // Handle collection with custom inner tag name
                     let tag_name = XmlNames::tag(&field, obj_meta, Some(&meta));
                     let inner_name = LitStr::new(&meta.get_or("inner", "item".to_string()), proc_macro2::Span::call_site());
-                    Some(XmlTagElement::Collection(field, tag_name, inner_name, extension))
+                    XmlTagKind::Collection(tag_name, inner_name)
+                } else if meta.contains("entry") {
+                    // Map written as <entry key="k">v</entry> children
+                    let tag_name = XmlNames::tag(&field, obj_meta, Some(meta));
+                    let (entry_name, key_name) = XmlNames::map_entry(meta);
+                    XmlTagKind::Map(ty, tag_name, entry_name, key_name)
                 } else {
                     let tag_name = XmlNames::tag(&field, obj_meta, Some(&meta));
-                    Some(XmlTagElement::Simple(field, ty, tag_name, extension))
-                }
+                    XmlTagKind::Simple(ty, tag_name)
+                };
+                return Some(XmlTagElement { value, kind, extension, raw, collection: collection && with.is_none(), with, optional, skip_if: meta.path("skip_serializing_if") })
             }
         } else {
             let tag_name = XmlNames::tag(&field, obj_meta, None);
-            return Some(XmlTagElement::Simple(field, ty, tag_name, extension))
+            return Some(XmlTagElement { value, kind: XmlTagKind::Simple(ty, tag_name), extension, raw: false, collection: false, with: None, optional: false, skip_if: None })
         }
         None
     }
-}
\ No newline at end of file
+
+    fn write_value(&self, value: TokenStream) -> TokenStream {
+        if self.collection {
+            let write_item = self.write_item(quote! { item });
+            quote! {
+                for item in &#value {
+                    #write_item
+                }
+            }
+        } else {
+            self.write_item(value)
+        }
+    }
+
+    fn write_item(&self, value: TokenStream) -> TokenStream {
+        let value = match &self.with {
+            Some(with) => quote! { #with(&#value) },
+            None => value,
+        };
+        if self.raw {
+            quote! { writer.write_unescaped(|writer| #value.write_xml(writer, false))?; }
+        } else {
+            quote! { #value.write_xml(writer, false)?; }
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{DeriveInput, Error, Fields, LitStr};
+use crate::common::meta::{MetaInfo, MetaName};
+use crate::common::naming::names::XmlNames;
+use crate::serialize::parser::choice::XmlChoice;
+use crate::serialize::parser::declaration::XmlDeclaration;
+use crate::serialize::parser::dtd::XmlDTD;
+use crate::serialize::parser::instructions::XmlPI;
+
+/* Enum written as a single element, the variant goes to the tag_attribute (ex. xsi:type) */
+pub(crate) struct XmlTagged;
+
+impl XmlTagged {
+    pub fn parse(input: &DeriveInput) -> TokenStream {
+        let obj_meta_info = MetaInfo::from_name(&input.attrs, MetaName::XML);
+        let tag = LitStr::new(&XmlNames::root(input, obj_meta_info.as_ref()), Span::call_site());
+        let tag_attribute = obj_meta_info.as_ref().map(|meta| meta.get_or("tag_attribute", "type".to_string())).unwrap_or_default();
+        let dtd = XmlDTD::parse(input, &tag);
+        let pi = XmlPI::parse(input);
+        let declaration = XmlDeclaration::parse(input, &tag);
+
+        let syn::Data::Enum(data) = &input.data else { return quote! {} };
+        let mut arms = vec![];
+        for variant in &data.variants {
+            let ident = &variant.ident;
+            let variant_meta = MetaInfo::from_name(&variant.attrs, MetaName::XML);
+            let name = XmlNames::variant(ident, obj_meta_info.as_ref(), variant_meta.as_ref());
+            let type_attribute = if tag_attribute.starts_with("xsi:") {
+                quote! {
+                    writer.declare_namespace("xsi", "http://www.w3.org/2001/XMLSchema-instance")?;
+                    writer.write_attribute(#tag_attribute, #name)?;
+                }
+            } else {
+                quote! { writer.write_attribute(#tag_attribute, #name)?; }
+            };
+            let arm = match &variant.fields {
+                Fields::Unit => quote! {
+                    Self::#ident => {
+                        writer.start_tag(#tag)?;
+                        #type_attribute
+                        writer.end_start_tag()?;
+                        writer.end_tag(#tag)?;
+                    }
+                },
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => quote! {
+                    Self::#ident(xa_value) => {
+                        writer.start_tag(#tag)?;
+                        #type_attribute
+                        writer.end_start_tag()?;
+                        xa_value.write_xml(writer, false)?;
+                        writer.end_tag(#tag)?;
+                    }
+                },
+                Fields::Named(fields) => XmlChoice::struct_variant(ident, fields, obj_meta_info.as_ref(), &tag, type_attribute),
+                Fields::Unnamed(_) => {
+                    return Error::new_spanned(variant, "Tuple variants must have a single field.").to_compile_error();
+                }
+            };
+            arms.push(arm);
+        }
+
+        quote! {
+            #declaration
+            #pi
+            #dtd
+            match self {
+                #(#arms)*
+            }
+        }
+    }
+}
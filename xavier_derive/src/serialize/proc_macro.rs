@@ -6,19 +6,31 @@ use quote::quote;
 use syn::Data::{Enum, Struct, Union};
 use syn::parse_macro_input;
 use crate::serialize::parser::streams::{SerStreamType, XmlSerStream};
+use crate::common::meta::{MetaInfo, MetaName};
+use crate::serialize::parser::enumeration::XmlEnumValue;
 
 pub fn impl_xml_serializable(input: TokenStream) -> TokenStream {
     let input  = parse_macro_input!(input as DeriveInput);
     let (impl_generics, ty_generics, where_clause) = &input.generics.split_for_impl();
     let object_name = &input.ident;
 
+    let mut text_impl = quote! {};
     let xml_code = match &input.data {
         Struct(obj) => match &obj.fields {
             Fields::Named(FieldsNamed { .. }) => { XmlSerStream::stream(&input, SerStreamType::Complex) },
             Fields::Unnamed(FieldsUnnamed { .. }) => { XmlSerStream::stream(&input, SerStreamType::Simple) }
             Fields::Unit => { XmlSerStream::stream(&input, SerStreamType::Empty) }
         },
-        Enum(DataEnum { .. }) => { XmlSerStream::stream(&input, SerStreamType::Enum) },
+        Enum(DataEnum { variants, .. }) => {
+            if MetaInfo::from_name(&input.attrs, MetaName::XML).map(|meta| meta.contains("tag_attribute")).unwrap_or(false) {
+                XmlSerStream::stream(&input, SerStreamType::Tagged)
+            } else if variants.iter().all(|variant| matches!(variant.fields, Fields::Unit)) {
+                text_impl = XmlEnumValue::to_xml_text(&input);
+                XmlSerStream::stream(&input, SerStreamType::Enum)
+            } else {
+                XmlSerStream::stream(&input, SerStreamType::Choice)
+            }
+        },
         Union(DataUnion { .. }) => {
             let message = "Proc macro 'xml serialize' does not support this type of object config.";
             return Error::new_spanned(object_name, message).to_compile_error().into();}
@@ -26,15 +38,12 @@ pub fn impl_xml_serializable(input: TokenStream) -> TokenStream {
 
     let expanded = quote! {
         impl #impl_generics xavier::serialize::macro_trait::XmlSerializable for #object_name #ty_generics #where_clause {
-            fn to_xml(&self, root: bool) -> String {
-                #xml_code
-            }
-        }
-        impl xavier::serialize::macro_trait::XmlSerializable for Box<#object_name> {
-            fn to_xml(&self, root: bool) -> String {
+            fn write_xml(&self, writer: &mut xavier::serialize::writer::XmlWriter, root: bool) -> std::io::Result<()> {
                 #xml_code
             }
         }
+
+        #text_impl
     };
     return TokenStream::from(expanded)
 
@@ -1,42 +1,90 @@
+use std::collections::{BTreeSet, HashSet, LinkedList, VecDeque};
+use std::hash::Hash;
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::Reader;
 use crate::deserialize::error::PError;
 use crate::deserialize::macro_trait::XmlDeserializable;
+use crate::deserialize::source::XmlSource;
 
+/* Collections built from the items read in document order */
+pub trait XmlCollection: Sized {
+    type Item;
+    fn from_items(items: Vec<Self::Item>) -> Result<Self, PError>;
+}
+
+macro_rules! deserializable_collection {
+    ($collection:ty $(, $bound:path)*) => {
+        impl<T> XmlCollection for $collection where $(T: $bound),* {
+            type Item = T;
+
+            fn from_items(items: Vec<T>) -> Result<Self, PError> {
+                Ok(items.into_iter().collect())
+            }
+        }
 
-impl <T: XmlDeserializable> XmlDeserializable for Vec<T>  {
-
-    fn from_xml(reader: &mut Reader<&[u8]>, start_event: Option<&BytesStart>) -> Result<Option<Self>, PError> {
-        let mut children: Vec<T> = vec!();
-        let tag_name = if let Some(start_event) = start_event {
-            String::from_utf8(start_event.name().0.to_vec())?
-        } else {
-            return Err(PError::new("No tag name found for collection"));
-        };
-
-        loop {
-            match reader.read_event() {
-                Err(error) =>  { return Err(PError::new(&format!("Error at position {}: {:?}", reader.buffer_position(), error))) },
-                Ok(Event::Eof) => { },
-                Ok(Event::Start(event)) => {
-                    children.push(
-                        T::from_xml(reader, Some(&event))?
-                            .ok_or_else(|| PError::new("Expected child element but got None"))?
-                    );
-                },
-                Ok(Event::End(event)) => {
-                    if String::from_utf8(event.name().0.to_vec())? == tag_name {
-                        return Ok(Some(children))
-                    }
-                },
-                Ok(Event::Empty(_)) => {},
-                Ok(Event::Comment(_)) => {},
-                Ok(Event::Text(_)) => {},
-                Ok(Event::CData(_)) => {},
-                Ok(Event::Decl(_)) => {},
-                Ok(Event::PI(_)) => {},
-                Ok(Event::DocType(_)) => {},
+        impl<'de, T: XmlDeserializable<'de> $(+ $bound)*> XmlDeserializable<'de> for $collection {
+            fn from_xml<R: XmlSource<'de>>(reader: &mut Reader<R>, start_event: Option<&BytesStart>) -> Result<Option<Self>, PError> {
+                Self::from_items(read_items(reader, start_event)?).map(Some)
             }
         }
+    };
+}
+
+deserializable_collection!(Vec<T>);
+deserializable_collection!(VecDeque<T>);
+deserializable_collection!(LinkedList<T>);
+deserializable_collection!(HashSet<T>, Eq, Hash);
+deserializable_collection!(BTreeSet<T>, Ord);
+deserializable_collection!(Box<[T]>);
+
+impl <T, const N: usize> XmlCollection for [T; N] {
+    type Item = T;
+
+    fn from_items(items: Vec<T>) -> Result<Self, PError> {
+        let length = items.len();
+        items.try_into().map_err(|_| PError::new(&format!("Expected {} items but found {}", N, length)))
+    }
+}
+
+impl<'de, T: XmlDeserializable<'de>, const N: usize> XmlDeserializable<'de> for [T; N] {
+    fn from_xml<R: XmlSource<'de>>(reader: &mut Reader<R>, start_event: Option<&BytesStart>) -> Result<Option<Self>, PError> {
+        Self::from_items(read_items(reader, start_event)?).map(Some)
+    }
+}
+
+/* Reads every child element as an item until the end of start_event */
+pub fn read_items<'de, T: XmlDeserializable<'de>, R: XmlSource<'de>>(reader: &mut Reader<R>, start_event: Option<&BytesStart>) -> Result<Vec<T>, PError> {
+    let mut children: Vec<T> = vec!();
+    let tag_name = if let Some(start_event) = start_event {
+        String::from_utf8(start_event.name().0.to_vec())?
+    } else {
+        return Err(PError::new("No tag name found for collection"));
+    };
+
+    let mut buffer = Vec::new();
+    loop {
+        buffer.clear();
+        match reader.read_event_into(&mut buffer) {
+            Err(error) =>  { return Err(PError::new(&format!("Error at position {}: {:?}", reader.buffer_position(), error))) },
+            Ok(Event::Eof) => { },
+            Ok(Event::Start(event)) => {
+                children.push(
+                    T::from_xml(reader, Some(&event))?
+                        .ok_or_else(|| PError::new("Expected child element but got None"))?
+                );
+            },
+            Ok(Event::End(event)) => {
+                if String::from_utf8(event.name().0.to_vec())? == tag_name {
+                    return Ok(children)
+                }
+            },
+            Ok(Event::Empty(_)) => {},
+            Ok(Event::Comment(_)) => {},
+            Ok(Event::Text(_)) => {},
+            Ok(Event::CData(_)) => {},
+            Ok(Event::Decl(_)) => {},
+            Ok(Event::PI(_)) => {},
+            Ok(Event::DocType(_)) => {},
+        }
     }
 }
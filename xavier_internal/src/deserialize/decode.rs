@@ -1,15 +1,86 @@
+use crate::deserialize::error::PError;
+
 #[macro_export]
 macro_rules! decode {
     ($expr:expr) => { xavier::deserialize::decode::decode_xml($expr).to_string() };
 }
 
+/* Single pass over the text, named (&amp;), decimal (&#233;) and hex (&#x20AC;) references are replaced.
+   References that are malformed, unknown or point to a char XML doesn't allow are kept as they are */
 pub fn decode_xml(input: &str) -> String {
-    input
-        .replace("&amp;", "&")
-        .replace("&lt;", "<")
-        .replace("&gt;", ">")
-        .replace("&quot;", "\"")
-        .replace("&apos;", "'")
+    decode_references(input, false).unwrap_or_else(|_| input.to_string())
+}
+
+/* Like decode_xml, but every reference has to be well formed and point to a char XML allows */
+pub fn decode_xml_strict(input: &str) -> Result<String, PError> {
+    decode_references(input, true)
+}
+
+fn decode_references(input: &str, strict: bool) -> Result<String, PError> {
+    let Some(first) = input.find('&') else { return Ok(input.to_string()) };
+    let mut output = String::with_capacity(input.len());
+    output.push_str(&input[..first]);
+    // Always starts at an '&' (or is empty)
+    let mut rest = &input[first..];
+    while !rest.is_empty() {
+        let body = &rest[1..];
+        // A reference name never holds spaces, markup or another reference
+        let end = body.find(|c: char| c == ';' || c == '&' || c == '<' || c.is_whitespace());
+        let consumed = match end.filter(|&end| body[end..].starts_with(';')) {
+            Some(end) => {
+                match reference_char(&body[..end]) {
+                    Ok(c) => output.push(c),
+                    Err(error) if strict => return Err(error),
+                    Err(_) => output.push_str(&rest[..end + 2]),
+                }
+                end + 2
+            },
+            None if strict => {
+                return Err(PError::new(&format!("Unterminated reference '&{}'", &body[..end.unwrap_or(body.len())])));
+            },
+            None => {
+                output.push('&');
+                1
+            },
+        };
+        rest = &rest[consumed..];
+        let next = rest.find('&').unwrap_or(rest.len());
+        output.push_str(&rest[..next]);
+        rest = &rest[next..];
+    }
+    Ok(output)
+}
+
+/* The char of a reference, given without '&' and ';' */
+fn reference_char(reference: &str) -> Result<char, PError> {
+    let code = match reference {
+        "amp" => return Ok('&'),
+        "lt" => return Ok('<'),
+        "gt" => return Ok('>'),
+        "quot" => return Ok('"'),
+        "apos" => return Ok('\''),
+        _ => {
+            let (digits, radix) = match reference.strip_prefix("#x") {
+                Some(hex) => (hex, 16),
+                None => match reference.strip_prefix('#') {
+                    Some(decimal) => (decimal, 10),
+                    None => return Err(PError::new(&format!("Unknown entity '&{};'", reference))),
+                },
+            };
+            // from_str_radix also takes a sign
+            if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+                return Err(PError::new(&format!("Invalid character reference '&{};'", reference)));
+            }
+            u32::from_str_radix(digits, radix).ok()
+        }
+    };
+    code.and_then(char::from_u32).filter(|&c| is_xml_char(c))
+        .ok_or_else(|| PError::new(&format!("Malicious XML entities detected, '&{};' isn't a valid XML char", reference)))
+}
+
+/* Char production of XML 1.0 */
+fn is_xml_char(c: char) -> bool {
+    matches!(c, '\u{9}' | '\u{A}' | '\u{D}' | '\u{20}'..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}' | '\u{10000}'..='\u{10FFFF}')
 }
 
 pub fn strip_cdata(s: &str) -> &str {
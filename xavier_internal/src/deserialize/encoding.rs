@@ -0,0 +1,177 @@
+use std::io;
+use std::io::{BufRead, Read};
+use encoding_rs::{Decoder, Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use crate::deserialize::declaration;
+use crate::serialize::encoding::OutputEncoding;
+
+/* Bytes read before giving up on finding the end of the XML declaration */
+const MAX_DECLARATION_LENGTH: usize = 1024;
+
+/* Document encoding, the labels the writer emits byte per code point are read back the same way */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InputEncoding {
+    /* US-ASCII and ISO-8859-1, encoding_rs would decode them as windows-1252 */
+    Latin1,
+    Other(&'static Encoding),
+}
+
+/* Finds the document encoding from the BOM, the first bytes or the declaration, along with the BOM length */
+pub fn sniff_encoding(head: &[u8]) -> io::Result<(InputEncoding, usize)> {
+    if let Some((encoding, bom_length)) = Encoding::for_bom(head) {
+        return Ok((InputEncoding::Other(encoding), bom_length));
+    }
+    if head.starts_with(&[0x3C, 0x00, 0x3F, 0x00]) {
+        return Ok((InputEncoding::Other(UTF_16LE), 0));
+    }
+    if head.starts_with(&[0x00, 0x3C, 0x00, 0x3F]) {
+        return Ok((InputEncoding::Other(UTF_16BE), 0));
+    }
+    let utf8 = Ok((InputEncoding::Other(UTF_8), 0));
+    if !head.starts_with(b"<?xml") {
+        return utf8;
+    }
+    let end = match head.windows(2).position(|window| window == b"?>") {
+        Some(end) => end + 2,
+        None => return utf8,
+    };
+    let label = match declaration::parse(&String::from_utf8_lossy(&head[..end])) {
+        Ok((_, Some(label), _)) => label,
+        _ => return utf8,
+    };
+    if let Ok(OutputEncoding::Limited { .. }) = OutputEncoding::from_label(&label) {
+        return Ok((InputEncoding::Latin1, 0));
+    }
+    match Encoding::for_label(label.trim().as_bytes()) {
+        // Without a BOM the bytes read so far are ASCII compatible, a UTF-16 label can't be right
+        Some(encoding) if encoding == UTF_16LE || encoding == UTF_16BE => utf8,
+        Some(encoding) => Ok((InputEncoding::Other(encoding), 0)),
+        None => Err(io::Error::new(io::ErrorKind::InvalidData, format!("Unsupported document encoding: {}", label))),
+    }
+}
+
+/* BOM length of a UTF-8 document, None when it has to be transcoded */
+pub fn utf8_bom_length(head: &[u8]) -> io::Result<Option<usize>> {
+    let (encoding, bom_length) = sniff_encoding(head)?;
+    Ok(if encoding == InputEncoding::Other(UTF_8) { Some(bom_length) } else { None })
+}
+
+enum Transcoder {
+    Latin1,
+    Decoder(Decoder),
+}
+
+/* Reader that transcodes the document to UTF-8 before it reaches the parser */
+pub struct DecodingReader<R: BufRead> {
+    inner: R,
+    /* None when the source is already UTF-8, bytes are passed through untouched */
+    decoder: Option<Transcoder>,
+    output: Vec<u8>,
+    position: usize,
+    finished: bool,
+}
+
+impl<R: BufRead> DecodingReader<R> {
+    pub fn new(mut inner: R) -> io::Result<Self> {
+        let head = read_head(&mut inner)?;
+        let (encoding, bom_length) = sniff_encoding(&head)?;
+        let mut reader = DecodingReader {
+            inner,
+            decoder: None,
+            output: vec![],
+            position: 0,
+            finished: false,
+        };
+        let mut decoder = match encoding {
+            InputEncoding::Other(encoding) if encoding == UTF_8 => {
+                reader.output = head[bom_length..].to_vec();
+                return Ok(reader);
+            },
+            InputEncoding::Latin1 => Transcoder::Latin1,
+            InputEncoding::Other(encoding) => Transcoder::Decoder(encoding.new_decoder_without_bom_handling()),
+        };
+        decode_into(&mut decoder, &mut reader.output, &head[bom_length..], false);
+        reader.decoder = Some(decoder);
+        Ok(reader)
+    }
+
+    fn refill(&mut self) -> io::Result<()> {
+        self.output.clear();
+        self.position = 0;
+        if let Some(decoder) = &mut self.decoder {
+            while self.output.is_empty() && !self.finished {
+                let input = self.inner.fill_buf()?;
+                let length = input.len();
+                self.finished = length == 0;
+                decode_into(decoder, &mut self.output, input, self.finished);
+                self.inner.consume(length);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn decode_into(decoder: &mut Transcoder, output: &mut Vec<u8>, input: &[u8], last: bool) {
+    let decoder = match decoder {
+        Transcoder::Latin1 => {
+            let mut buffer = [0; 2];
+            for &byte in input {
+                output.extend_from_slice((byte as char).encode_utf8(&mut buffer).as_bytes());
+            }
+            return;
+        },
+        Transcoder::Decoder(decoder) => decoder,
+    };
+    let start = output.len();
+    let capacity = decoder.max_utf8_buffer_length(input.len()).unwrap_or(input.len() * 3 + 16);
+    output.resize(start + capacity, 0);
+    let (_, _, written, _) = decoder.decode_to_utf8(input, &mut output[start..], last);
+    output.truncate(start + written);
+}
+
+fn read_head<R: BufRead>(inner: &mut R) -> io::Result<Vec<u8>> {
+    let mut head = Vec::new();
+    loop {
+        let complete = head.len() >= 5 && (!head.starts_with(b"<?xml") || head.windows(2).any(|window| window == b"?>"));
+        if complete || head.len() >= MAX_DECLARATION_LENGTH {
+            return Ok(head);
+        }
+        let input = inner.fill_buf()?;
+        if input.is_empty() {
+            return Ok(head);
+        }
+        let length = input.len().min(MAX_DECLARATION_LENGTH - head.len());
+        head.extend_from_slice(&input[..length]);
+        inner.consume(length);
+    }
+}
+
+impl<R: BufRead> Read for DecodingReader<R> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let length = available.len().min(buffer.len());
+        buffer[..length].copy_from_slice(&available[..length]);
+        self.consume(length);
+        Ok(length)
+    }
+}
+
+impl<R: BufRead> BufRead for DecodingReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.position < self.output.len() {
+            return Ok(&self.output[self.position..]);
+        }
+        if self.decoder.is_none() {
+            return self.inner.fill_buf();
+        }
+        self.refill()?;
+        Ok(&self.output[self.position..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        if self.position < self.output.len() {
+            self.position = (self.position + amount).min(self.output.len());
+        } else if self.decoder.is_none() {
+            self.inner.consume(amount);
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use std::{error, fmt};
 use std::char::ParseCharError;
 use std::convert::Infallible;
+use std::io;
 use std::num::{ParseFloatError, ParseIntError};
 use std::str::ParseBoolError;
 use std::string::FromUtf8Error;
@@ -33,6 +34,12 @@ impl From<FromUtf8Error> for PError {
     }
 }
 
+impl From<io::Error> for PError {
+    fn from(value: io::Error) -> Self {
+        PError { message: value.to_string() }
+    }
+}
+
 impl From<quick_xml::Error> for PError {
     fn from(value: quick_xml::Error) -> Self {
         PError { message: value.to_string() }
@@ -2,6 +2,7 @@ use quick_xml::events::BytesStart;
 use quick_xml::Reader;
 
 use crate::deserialize::error::PError;
+use crate::deserialize::source::XmlSource;
 
 pub trait WrapWith<F, T> {
     fn wrap(self) -> Option<F>;
@@ -16,7 +17,19 @@ where
     }
 }
 
-pub trait XmlDeserializable {
-    fn from_xml(reader: &mut Reader<&[u8]>, event: Option<&BytesStart>) -> Result<Option<Self>, PError> where Self: Sized;
+/* 'de is the lifetime of the input, borrowed fields (&'de str, Cow<'de, str>) point into it */
+pub trait XmlDeserializable<'de> {
+    fn from_xml<R: XmlSource<'de>>(reader: &mut Reader<R>, event: Option<&BytesStart>) -> Result<Option<Self>, PError> where Self: Sized;
     fn inner_name() -> Option<String> { None }
-}
\ No newline at end of file
+    /* Element names this type is read from, the inner name and its aliases */
+    fn accepts_name(name: &str) -> bool { Self::inner_name().is_some_and(|inner_name| inner_name == name) }
+    /* Mixed content items, the item holding the text between child elements if the type has one */
+    fn from_text(_text: String) -> Result<Option<Self>, PError> where Self: Sized { Ok(None) }
+    /* Mixed content items, child element names this type is read from */
+    fn accepts_item(name: &str) -> bool { Self::accepts_name(name) }
+}
+
+/* Types that don't borrow from the input and can be read from any source */
+pub trait XmlDeserializableOwned: for<'de> XmlDeserializable<'de> {}
+
+impl<T> XmlDeserializableOwned for T where T: for<'de> XmlDeserializable<'de> {}
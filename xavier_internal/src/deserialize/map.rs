@@ -0,0 +1,100 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use crate::deserialize::text::{decode_text, FromXmlText};
+use crate::deserialize::error::PError;
+use crate::deserialize::macro_trait::XmlDeserializable;
+use crate::deserialize::source::XmlSource;
+use crate::serialize::map::XmlMap;
+
+impl<'de, K, V> XmlDeserializable<'de> for HashMap<K, V>
+    where K: FromXmlText + Eq + Hash, V: XmlDeserializable<'de> {
+    fn from_xml<R: XmlSource<'de>>(reader: &mut Reader<R>, start_event: Option<&BytesStart>) -> Result<Option<Self>, PError> {
+        read_named(reader, start_event).map(Some)
+    }
+}
+
+impl<'de, K, V> XmlDeserializable<'de> for BTreeMap<K, V>
+    where K: FromXmlText + Ord, V: XmlDeserializable<'de> {
+    fn from_xml<R: XmlSource<'de>>(reader: &mut Reader<R>, start_event: Option<&BytesStart>) -> Result<Option<Self>, PError> {
+        read_named(reader, start_event).map(Some)
+    }
+}
+
+// Element name as key: <map><key>value</key></map>
+fn read_named<'de, M, R>(reader: &mut Reader<R>, start_event: Option<&BytesStart>) -> Result<M, PError>
+    where M: XmlMap + Default, M::Key: FromXmlText, M::Value: XmlDeserializable<'de>, R: XmlSource<'de> {
+    let tag_name = if let Some(start_event) = start_event {
+        start_event.name().0.to_vec()
+    } else {
+        return Err(PError::new("No tag name found for map"));
+    };
+
+    let mut map = M::default();
+    let mut buffer = Vec::new();
+    loop {
+        buffer.clear();
+        match reader.read_event_into(&mut buffer) {
+            Err(error) =>  { return Err(PError::new(&format!("Error at position {}: {:?}", reader.buffer_position(), error))) },
+            Ok(Event::Eof) => { return Ok(map) },
+            Ok(Event::Start(event)) => {
+                let key = String::from_utf8(event.name().0.to_vec())?;
+                let value = M::Value::from_xml(reader, Some(&event))?
+                    .ok_or_else(|| PError::new(&format!("Value not found for map key '{}'", key)))?;
+                map.insert_entry(M::Key::from_xml_text(&key)?, value);
+            },
+            Ok(Event::End(event)) => {
+                if event.name().0 == tag_name.as_slice() {
+                    return Ok(map)
+                }
+            },
+            Ok(Event::Empty(_)) => {},
+            Ok(Event::Comment(_)) => {},
+            Ok(Event::Text(_)) => {},
+            Ok(Event::CData(_)) => {},
+            Ok(Event::Decl(_)) => {},
+            Ok(Event::PI(_)) => {},
+            Ok(Event::DocType(_)) => {},
+        }
+    }
+}
+
+// Entry layout: <map><entry key="key">value</entry></map>
+pub fn read_entries<'de, M, R>(reader: &mut Reader<R>, start_event: &BytesStart, entry: &str, key: &str) -> Result<M, PError>
+    where M: XmlMap + Default, M::Key: FromXmlText, M::Value: XmlDeserializable<'de>, R: XmlSource<'de> {
+    let tag_name = start_event.name().0.to_vec();
+    let mut map = M::default();
+    let mut buffer = Vec::new();
+    loop {
+        buffer.clear();
+        match reader.read_event_into(&mut buffer) {
+            Err(error) =>  { return Err(PError::new(&format!("Error at position {}: {:?}", reader.buffer_position(), error))) },
+            Ok(Event::Eof) => { return Ok(map) },
+            Ok(Event::Start(event)) => {
+                if event.name().0 != entry.as_bytes() {
+                    return Err(PError::new(&format!("Unexpected element '{}' in map, expected '{}'", String::from_utf8(event.name().0.to_vec())?, entry)));
+                }
+                let entry_key = match event.try_get_attribute(key)? {
+                    Some(attribute) => decode_text(&attribute.value)?,
+                    None => return Err(PError::new(&format!("Attribute '{}' not found for map entry", key))),
+                };
+                let value = M::Value::from_xml(reader, Some(&event))?
+                    .ok_or_else(|| PError::new(&format!("Value not found for map key '{}'", entry_key)))?;
+                map.insert_entry(M::Key::from_xml_text(&entry_key)?, value);
+            },
+            Ok(Event::End(event)) => {
+                if event.name().0 == tag_name.as_slice() {
+                    return Ok(map)
+                }
+            },
+            Ok(Event::Empty(_)) => {},
+            Ok(Event::Comment(_)) => {},
+            Ok(Event::Text(_)) => {},
+            Ok(Event::CData(_)) => {},
+            Ok(Event::Decl(_)) => {},
+            Ok(Event::PI(_)) => {},
+            Ok(Event::DocType(_)) => {},
+        }
+    }
+}
@@ -2,7 +2,14 @@ pub mod macro_trait;
 pub mod primitives;
 pub mod declaration;
 pub mod decode;
+pub mod text;
+pub mod encoding;
 pub mod instructions;
 pub mod error;
 pub mod doctype;
 pub mod collections;
+pub mod map;
+pub mod raw;
+pub mod source;
+pub mod options;
+mod wrappers;
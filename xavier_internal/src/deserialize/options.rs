@@ -0,0 +1,37 @@
+use std::cell::Cell;
+
+#[derive(Clone, Debug, Default)]
+pub struct DeserializeOptions {
+    /* Unknown child elements and attributes are errors, as if every type had #[xml(deny_unknown)] */
+    pub strict: bool,
+}
+
+impl DeserializeOptions {
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /* Runs parse with these options visible to the generated code (is_strict) on this thread */
+    pub fn scope<T, F: FnOnce() -> T>(&self, parse: F) -> T {
+        let _guard = StrictGuard(STRICT.replace(self.strict));
+        parse()
+    }
+}
+
+thread_local! {
+    static STRICT: Cell<bool> = const { Cell::new(false) };
+}
+
+/* Restores the previous value, also when parse panics */
+struct StrictGuard(bool);
+
+impl Drop for StrictGuard {
+    fn drop(&mut self) {
+        STRICT.set(self.0);
+    }
+}
+
+pub fn is_strict() -> bool {
+    STRICT.get()
+}
@@ -1,9 +1,12 @@
+use std::borrow::Cow;
 use std::str::FromStr;
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::Reader;
 use crate::deserialize::error::PError;
 use crate::deserialize::macro_trait::XmlDeserializable;
-use crate::deserialize::decode::{decode_xml, strip_cdata};
+use crate::deserialize::source::XmlSource;
+use crate::deserialize::decode::decode_xml_strict;
+use crate::deserialize::text::{check_characters, decode_cdata, decode_text};
 
 trait Primitive {}
 impl Primitive for i8 {}
@@ -22,147 +25,117 @@ impl Primitive for f32 {}
 impl Primitive for f64  {}
 impl Primitive for bool {}
 
-// Function to check for malicious characters
-fn contains_malicious_characters(input: &str) -> bool {
-    // Check for null characters and malicious control characters
-    for c in input.chars() {
-        match c as u32 {
-            0x00..=0x08 | 0x0B | 0x0C | 0x0E..=0x1F | 0x7F => {
-                return true;
-            }
-            _ => {}
+/* Character data up to the end tag of the current element, text is decoded and CDATA kept as is,
+   comments, PIs and nested elements with their text are skipped. None when the element is an empty tag */
+pub fn read_text<'de, R: XmlSource<'de>>(reader: &mut Reader<R>) -> Result<Option<String>, PError> {
+    let mut text: Option<String> = None;
+    let mut depth = 0;
+    let mut buffer = Vec::new();
+    loop {
+        buffer.clear();
+        match reader.read_event_into(&mut buffer) {
+            Err(error) => { return Err(PError::new(&format!("Error at position {}: {:?}", reader.buffer_position(), error))) },
+            Ok(Event::Start(_)) => { depth += 1; },
+            Ok(Event::End(_)) if depth > 0 => { depth -= 1; },
+            Ok(Event::End(_)) | Ok(Event::Eof) => { break; },
+            Ok(Event::Empty(_)) => {
+                if depth == 0 && text.is_none() {
+                    return Ok(None);
+                }
+            },
+            Ok(Event::Text(event)) if depth == 0 => { text.get_or_insert_with(String::new).push_str(&decode_text(&event)?); },
+            Ok(Event::CData(event)) if depth == 0 => { text.get_or_insert_with(String::new).push_str(&decode_cdata(&event)?); },
+            Ok(_) => {},
         }
     }
-    false
+    Ok(Some(text.unwrap_or_default()))
 }
 
-// Function to check for malicious hexadecimal entities
-fn contains_malicious_entities(input: &str) -> bool {
-    // Check for malicious hexadecimal entities
-    let malicious_patterns = [
-        "&#x00;", "&#x01;", "&#x02;", "&#x03;", "&#x04;", "&#x05;", "&#x06;", "&#x07;", "&#x08;",
-        "&#x0B;", "&#x0C;", "&#x0E;", "&#x0F;", "&#x10;", "&#x11;", "&#x12;", "&#x13;", "&#x14;",
-        "&#x15;", "&#x16;", "&#x17;", "&#x18;", "&#x19;", "&#x1A;", "&#x1B;", "&#x1C;", "&#x1D;",
-        "&#x1E;", "&#x1F;", "&#x7F;"
-    ];
-    
-    for pattern in &malicious_patterns {
-        if input.contains(pattern) {
-            return true;
-        }
+impl<'de> XmlDeserializable<'de> for String {
+    fn from_xml<R: XmlSource<'de>>(reader: &mut Reader<R>, _: Option<&BytesStart>) -> Result<Option<Self>, PError> {
+        read_text(reader)
+    }
+}
+
+/* Text borrowed from the input when it is in memory and there is nothing to decode, used for Cow<'a, str> fields.
+   Text split in several chunks (CDATA, comments) is joined into an owned value */
+pub fn read_borrowed_text<'de, R: XmlSource<'de>>(reader: &mut Reader<R>) -> Result<Option<Cow<'de, str>>, PError> {
+    let mut text: Option<Cow<'de, str>> = None;
+    let mut depth = 0;
+    loop {
+        let chunk = match R::read_event_borrowed(reader) {
+            Err(error) => { return Err(PError::new(&format!("Error at position {}: {:?}", reader.buffer_position(), error))) },
+            Ok(Event::Start(_)) => { depth += 1; continue; },
+            Ok(Event::End(_)) if depth > 0 => { depth -= 1; continue; },
+            Ok(Event::End(_)) | Ok(Event::Eof) => { break; },
+            Ok(Event::Empty(_)) if depth == 0 && text.is_none() => { return Ok(None); },
+            Ok(Event::Text(event)) if depth == 0 => {
+                let raw = bytes_to_str(event.into_inner())?;
+                if raw.contains('&') { Cow::Owned(decode_xml_strict(&raw)?) } else { raw }
+            },
+            Ok(Event::CData(event)) if depth == 0 => bytes_to_str(event.into_inner())?,
+            Ok(_) => continue,
+        };
+        text = Some(match text {
+            None => chunk,
+            Some(previous) => Cow::Owned(previous.into_owned() + &chunk),
+        });
+    }
+    let text = text.unwrap_or(Cow::Borrowed(""));
+    check_characters(&text)?;
+    Ok(Some(text))
+}
+
+fn bytes_to_str(bytes: Cow<[u8]>) -> Result<Cow<str>, PError> {
+    match bytes {
+        Cow::Borrowed(bytes) => Ok(Cow::Borrowed(std::str::from_utf8(bytes).map_err(|error| PError::new(&error.to_string()))?)),
+        Cow::Owned(bytes) => Ok(Cow::Owned(String::from_utf8(bytes)?)),
     }
-    
-    false
 }
 
-// Special implementation for String that handles XML entities
-impl XmlDeserializable for String {
-    fn from_xml(reader: &mut Reader<&[u8]>, _: Option<&BytesStart>) -> Result<Option<Self>, PError> {
-        loop {
-            match reader.read_event() {
-                Err(error) => { return Err(PError::new(&format!("Error at position {}: {:?}", reader.buffer_position(), error))) },
-                Ok(Event::Eof) => { },
-                Ok(Event::Start(_)) => {},
-                Ok(Event::End(_)) => { return Ok(Some("".to_string())); },
-                Ok(Event::Empty(_)) => { return Ok(None); },
-                Ok(Event::Comment(_)) => {},
-                Ok(Event::Text(event)) => { 
-                    let raw_string = String::from_utf8(event.to_vec())?;
-                    if contains_malicious_entities(&raw_string) {
-                        return Err(PError::new("Malicious XML entities detected"));
-                    }
-                    let cdata_stripped = strip_cdata(&raw_string);
-                    let decoded = decode_xml(&cdata_stripped);
-                    
-                    if contains_malicious_characters(&decoded) {
-                        return Err(PError::new("Malicious characters detected in XML content"));
-                    }
-                    
-                    return Ok(Some(decoded));
-                },
-                Ok(Event::CData(event)) => { 
-                    let raw_string = String::from_utf8(event.to_vec())?;
-                    let trimmed = raw_string.trim();
-                    
-                    if contains_malicious_entities(&trimmed) {
-                        return Err(PError::new("Malicious XML entities detected"));
-                    }
-                    
-                    let decoded = decode_xml(&trimmed);
-                    
-                    if contains_malicious_characters(&decoded) {
-                        return Err(PError::new("Malicious characters detected in XML content"));
-                    }
-                    
-                    return Ok(Some(decoded));
-                },
-                Ok(Event::Decl(_)) => {},
-                Ok(Event::PI(_)) => {},
-                Ok(Event::DocType(_)) => {},
-            }
+impl<'de: 'a, 'a> XmlDeserializable<'de> for &'a str {
+    fn from_xml<R: XmlSource<'de>>(reader: &mut Reader<R>, _: Option<&BytesStart>) -> Result<Option<Self>, PError> {
+        match read_borrowed_text(reader)? {
+            Some(Cow::Borrowed(text)) => Ok(Some(text)),
+            Some(Cow::Owned(text)) => Err(PError::new(&format!("Text '{}' can't be borrowed, it has entities or the input isn't an in memory UTF-8 document (use Cow<str> or String)", text))),
+            None => Ok(None),
         }
     }
 }
 
 // Special implementation for char that handles whitespace correctly
-impl XmlDeserializable for char {
-    fn from_xml(reader: &mut Reader<&[u8]>, _: Option<&BytesStart>) -> Result<Option<Self>, PError> {
-        loop {
-            match reader.read_event() {
-                Err(error) => { return Err(PError::new(&format!("Error at position {}: {:?}", reader.buffer_position(), error))) },
-                Ok(Event::Eof) => { },
-                Ok(Event::Start(_)) => {},
-                Ok(Event::End(_)) => { return Ok(None); },
-                Ok(Event::Empty(_)) => { return Ok(None); },
-                Ok(Event::Comment(_)) => {},
-                Ok(Event::Text(event)) => { 
-                    let raw_string = String::from_utf8(event.to_vec())?;
-                    let trimmed = raw_string.trim();
-                    if trimmed.is_empty() {
-                        return Ok(Some(' '));
-                    }
-                    if raw_string.chars().count()  > 1 {
-                        return Err(PError::new("It's supposed to be a char and string was found!"));
-                    }
-                    return Ok(Some(trimmed.chars().next().ok_or_else(|| PError::new("Empty string cannot be parsed as char"))?));
-                },
-                Ok(Event::CData(event)) => { 
-                    let raw_string = String::from_utf8(event.to_vec())?;
-                    let trimmed = raw_string.trim();
-                    if trimmed.is_empty() {
-                        return Ok(Some(' '));
-                    }
-                    return Ok(Some(trimmed.chars().next().ok_or_else(|| PError::new("Empty string cannot be parsed as char"))?));
-                },
-                Ok(Event::Decl(_)) => {},
-                Ok(Event::PI(_)) => {},
-                Ok(Event::DocType(_)) => {},
-            }
+impl<'de> XmlDeserializable<'de> for char {
+    fn from_xml<R: XmlSource<'de>>(reader: &mut Reader<R>, _: Option<&BytesStart>) -> Result<Option<Self>, PError> {
+        let Some(text) = read_text(reader)? else { return Ok(None) };
+        if text.is_empty() {
+            return Ok(None);
+        }
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            return Ok(Some(' '));
+        }
+        if text.chars().count() > 1 {
+            return Err(PError::new("It's supposed to be a char and string was found!"));
         }
+        trimmed.chars().next().map(Some).ok_or_else(|| PError::new("Empty string cannot be parsed as char"))
     }
 }
 
-impl <T: FromStr + Primitive> XmlDeserializable for T
+impl<'de, T: FromStr + Primitive> XmlDeserializable<'de> for T
     where PError: From<<T as FromStr>::Err> {
-    fn from_xml(reader: &mut Reader<&[u8]>, _: Option<&BytesStart>)  -> Result<Option<Self>, PError> {
-        loop {
-            match reader.read_event() {
-                Err(error) =>  { return Err(PError::new(&format!("Error at position {}: {:?}", reader.buffer_position(), error))) },
-                Ok(Event::Eof) => {},
-                Ok(Event::Start(_)) => {},
-                Ok(Event::End(_)) => { return Ok(None); },
-                Ok(Event::Empty(_)) => { return Ok(None); },
-                Ok(Event::Comment(_)) => {},
-                Ok(Event::Text(event)) => {
-                    return Ok(Some(String::from_utf8(event.to_vec())?.parse()?))
-                },
-                Ok(Event::CData(event)) => {
-                    return Ok(Some(String::from_utf8(event.to_vec())?.parse()?))
-                },
-                Ok(Event::Decl(_)) => {},
-                Ok(Event::PI(_)) => {},
-                Ok(Event::DocType(_)) => {},
-            }
+    fn from_xml<R: XmlSource<'de>>(reader: &mut Reader<R>, _: Option<&BytesStart>)  -> Result<Option<Self>, PError> {
+        match read_text(reader)? {
+            Some(text) if !text.is_empty() => Ok(Some(text.parse()?)),
+            _ => Ok(None),
         }
     }
 }
+
+/* Element text handed to a #[xml(deserialize_with)] function */
+pub fn read_with<'de, T, E, R: XmlSource<'de>, F>(reader: &mut Reader<R>, start_event: Option<&BytesStart>, parse: F) -> Result<Option<T>, PError>
+    where F: FnOnce(&str) -> Result<T, E>, PError: From<E> {
+    match String::from_xml(reader, start_event)? {
+        Some(text) => Ok(Some(parse(&text)?)),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,40 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use crate::deserialize::error::PError;
+use crate::deserialize::macro_trait::XmlDeserializable;
+use crate::deserialize::source::XmlSource;
+use crate::serialize::raw::RawXml;
+
+// Captures the markup of the current element content as it is
+impl<'de> XmlDeserializable<'de> for RawXml {
+    fn from_xml<R: XmlSource<'de>>(reader: &mut Reader<R>, _: Option<&BytesStart>) -> Result<Option<Self>, PError> {
+        let mut raw = String::new();
+        let mut depth = 0;
+        let mut buffer = Vec::new();
+        loop {
+            buffer.clear();
+            match reader.read_event_into(&mut buffer) {
+                Err(error) => { return Err(PError::new(&format!("Error at position {}: {:?}", reader.buffer_position(), error))) },
+                Ok(Event::Eof) => { return Ok(Some(RawXml(raw))) },
+                Ok(Event::Start(event)) => {
+                    depth += 1;
+                    raw.push_str(&format!("<{}>", String::from_utf8(event.to_vec())?));
+                },
+                Ok(Event::End(event)) => {
+                    if depth == 0 {
+                        return Ok(Some(RawXml(raw)));
+                    }
+                    depth -= 1;
+                    raw.push_str(&format!("</{}>", String::from_utf8(event.name().0.to_vec())?));
+                },
+                Ok(Event::Empty(event)) => { raw.push_str(&format!("<{}/>", String::from_utf8(event.to_vec())?)) },
+                Ok(Event::Text(event)) => { raw.push_str(&String::from_utf8(event.to_vec())?) },
+                Ok(Event::CData(event)) => { raw.push_str(&format!("<![CDATA[{}]]>", String::from_utf8(event.to_vec())?)) },
+                Ok(Event::Comment(event)) => { raw.push_str(&format!("<!--{}-->", String::from_utf8(event.to_vec())?)) },
+                Ok(Event::PI(event)) => { raw.push_str(&format!("<?{}?>", String::from_utf8(event.to_vec())?)) },
+                Ok(Event::Decl(_)) => {},
+                Ok(Event::DocType(_)) => {},
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use std::io::{BufRead, Read};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+/* Input of the deserializers, in memory documents can lend their text for the 'de lifetime */
+pub trait XmlSource<'de>: BufRead + Sized {
+    /* Next event, borrowing from the input when possible and owned otherwise */
+    fn read_event_borrowed(reader: &mut Reader<Self>) -> quick_xml::Result<Event<'de>>;
+}
+
+impl<'de> XmlSource<'de> for &'de [u8] {
+    fn read_event_borrowed(reader: &mut Reader<Self>) -> quick_xml::Result<Event<'de>> {
+        reader.read_event()
+    }
+}
+
+/* Any BufRead as an input, text is always copied out of it */
+pub struct OwnedSource<R>(pub R);
+
+impl<R: BufRead> Read for OwnedSource<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl<R: BufRead> BufRead for OwnedSource<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.0.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.0.consume(amount)
+    }
+}
+
+impl<'de, R: BufRead> XmlSource<'de> for OwnedSource<R> {
+    fn read_event_borrowed(reader: &mut Reader<Self>) -> quick_xml::Result<Event<'de>> {
+        let mut buffer = Vec::new();
+        reader.read_event_into(&mut buffer).map(Event::into_owned)
+    }
+}
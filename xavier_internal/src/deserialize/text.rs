@@ -0,0 +1,78 @@
+use std::marker::PhantomData;
+use std::str::FromStr;
+use crate::deserialize::decode::decode_xml_strict;
+use crate::deserialize::error::PError;
+
+/* Character data decoding shared by every deserialization path (element text, CDATA, value fields, enums,
+   tuple structs and attribute values), so an entity or a control character is handled the same everywhere */
+
+/* Escaped text, element text or an attribute value */
+pub fn decode_text(raw: &[u8]) -> Result<String, PError> {
+    let raw = std::str::from_utf8(raw).map_err(|error| PError::new(&error.to_string()))?;
+    let text = decode_xml_strict(raw)?;
+    check_characters(&text)?;
+    Ok(text)
+}
+
+/* CDATA content is taken as it is */
+pub fn decode_cdata(raw: &[u8]) -> Result<String, PError> {
+    let text = String::from_utf8(raw.to_vec())?;
+    check_characters(&text)?;
+    Ok(text)
+}
+
+// Null and control characters other than tab, line feed and carriage return
+pub(crate) fn check_characters(text: &str) -> Result<(), PError> {
+    let malicious = text.chars().any(|c| matches!(c as u32, 0x00..=0x08 | 0x0B | 0x0C | 0x0E..=0x1F | 0x7F));
+    if malicious {
+        return Err(PError::new("Malicious characters detected in XML content"));
+    }
+    Ok(())
+}
+
+/* Value read from text, used for map keys and derived for unit enums from the variant names and aliases */
+pub trait FromXmlText: Sized {
+    fn from_xml_text(text: &str) -> Result<Self, PError>;
+}
+
+macro_rules! parsed_text {
+    ($($ty:ty),*) => {
+        $(impl FromXmlText for $ty {
+            fn from_xml_text(text: &str) -> Result<Self, PError> {
+                Ok(text.parse()?)
+            }
+        })*
+    };
+}
+
+parsed_text!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char);
+
+impl FromXmlText for String {
+    fn from_xml_text(text: &str) -> Result<Self, PError> {
+        Ok(text.to_string())
+    }
+}
+
+/* Attributes, value fields and text variants take FromXmlText when the type has it and FromStr otherwise:
+   (&&TextParser::<T>(PhantomData)).parse_text(text) with ParseXmlText and ParseStrText in scope */
+pub struct TextParser<T>(pub PhantomData<T>);
+
+pub trait ParseXmlText<T> {
+    fn parse_text(&self, text: &str) -> Result<T, PError>;
+}
+
+impl<T: FromXmlText> ParseXmlText<T> for &TextParser<T> {
+    fn parse_text(&self, text: &str) -> Result<T, PError> {
+        T::from_xml_text(text)
+    }
+}
+
+pub trait ParseStrText<T> {
+    fn parse_text(&self, text: &str) -> Result<T, PError>;
+}
+
+impl<T: FromStr> ParseStrText<T> for TextParser<T> where PError: From<T::Err> {
+    fn parse_text(&self, text: &str) -> Result<T, PError> {
+        Ok(text.parse()?)
+    }
+}
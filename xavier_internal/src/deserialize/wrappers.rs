@@ -0,0 +1,47 @@
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, RwLock};
+use quick_xml::events::BytesStart;
+use quick_xml::Reader;
+use crate::deserialize::error::PError;
+use crate::deserialize::macro_trait::XmlDeserializable;
+use crate::deserialize::source::XmlSource;
+
+macro_rules! deserializable_wrapper {
+    ($($wrapper:ident),*) => {
+        $(
+            impl<'de, T: XmlDeserializable<'de>> XmlDeserializable<'de> for $wrapper<T> {
+                fn from_xml<R: XmlSource<'de>>(reader: &mut Reader<R>, start_event: Option<&BytesStart>) -> Result<Option<Self>, PError> {
+                    Ok(T::from_xml(reader, start_event)?.map($wrapper::new))
+                }
+
+                fn inner_name() -> Option<String> {
+                    T::inner_name()
+                }
+
+                fn accepts_name(name: &str) -> bool {
+                    T::accepts_name(name)
+                }
+            }
+        )*
+    };
+}
+
+// Box is fundamental so it would overlap the primitives impl, the derive unboxes it instead
+deserializable_wrapper!(Rc, Arc, RefCell, Mutex, RwLock);
+
+/* Always owned, the derive reads Cow<'a, str> fields with read_borrowed_text to borrow from the input */
+impl<'de, B> XmlDeserializable<'de> for Cow<'_, B> where B: ToOwned + ?Sized, B::Owned: XmlDeserializable<'de> {
+    fn from_xml<R: XmlSource<'de>>(reader: &mut Reader<R>, start_event: Option<&BytesStart>) -> Result<Option<Self>, PError> {
+        Ok(B::Owned::from_xml(reader, start_event)?.map(Cow::Owned))
+    }
+
+    fn inner_name() -> Option<String> {
+        B::Owned::inner_name()
+    }
+
+    fn accepts_name(name: &str) -> bool {
+        B::Owned::accepts_name(name)
+    }
+}
@@ -0,0 +1,71 @@
+use crate::deserialize::error::PError;
+use crate::deserialize::text::decode_text;
+use crate::dom::{Element, XmlNode};
+
+/* Tree the writer builds in place of text, see XmlWriter::tree */
+#[derive(Default)]
+pub(crate) struct TreeBuilder {
+    open: Vec<Element>,
+    root: Option<Element>,
+}
+
+impl TreeBuilder {
+    pub fn start(&mut self, name: &str) {
+        self.open.push(Element::new(name));
+    }
+
+    /* The value comes escaped, as it would be written */
+    pub fn attribute(&mut self, name: &str, value: &str) -> Result<(), PError> {
+        if let Some(element) = self.open.last_mut() {
+            element.attributes.push((name.to_string(), decode_text(value.as_bytes())?));
+        }
+        Ok(())
+    }
+
+    pub fn text(&mut self, text: &str) {
+        self.push(XmlNode::Text(text.to_string()));
+    }
+
+    /* Pre rendered XML, attributes when it is written inside a start tag */
+    pub fn fragment(&mut self, fragment: &str, in_start_tag: bool) -> Result<(), PError> {
+        if in_start_tag {
+            let attributes = Element::read_fragment(&format!("<_ {}/>", fragment))?
+                .into_iter()
+                .find_map(|node| match node {
+                    XmlNode::Element(element) => Some(element.attributes),
+                    _ => None,
+                })
+                .unwrap_or_default();
+            if let Some(element) = self.open.last_mut() {
+                element.attributes.extend(attributes);
+            }
+        } else {
+            for node in Element::read_fragment(fragment)? {
+                self.push(node);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn end(&mut self) {
+        if let Some(element) = self.open.pop() {
+            match self.open.last_mut() {
+                Some(parent) => parent.children.push(XmlNode::Element(element)),
+                None => self.root = Some(element),
+            }
+        }
+    }
+
+    pub fn finish(self) -> Option<Element> {
+        self.root
+    }
+
+    // Text written in several pieces ends up in one node, markup outside the root element is dropped
+    fn push(&mut self, node: XmlNode) {
+        let Some(element) = self.open.last_mut() else { return };
+        match (element.children.last_mut(), node) {
+            (Some(XmlNode::Text(last)), XmlNode::Text(text)) => last.push_str(&text),
+            (_, node) => element.children.push(node),
+        }
+    }
+}
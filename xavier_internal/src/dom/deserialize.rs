@@ -0,0 +1,86 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use crate::deserialize::text::{decode_cdata, decode_text};
+use crate::deserialize::error::PError;
+use crate::deserialize::macro_trait::XmlDeserializable;
+use crate::deserialize::source::XmlSource;
+use crate::dom::{Element, XmlNode};
+
+impl Element {
+    /* Element named by the start event, its attributes and everything up to the matching end tag */
+    pub fn read<'de, R: XmlSource<'de>>(reader: &mut Reader<R>, start_event: &BytesStart) -> Result<Element, PError> {
+        let mut element = Element::new(&String::from_utf8(start_event.name().0.to_vec())?);
+        element.attributes = Element::read_attributes(start_event)?;
+        element.children = Element::read_nodes(reader, Some(&element.name))?;
+        Ok(element)
+    }
+
+    /* Nodes of a balanced fragment, like the content of a raw field */
+    pub fn read_fragment(fragment: &str) -> Result<Vec<XmlNode>, PError> {
+        Element::read_nodes(&mut Reader::from_reader(fragment.as_bytes()), None)
+    }
+
+    // Nodes up to the end tag of the parent, or up to the end of the input without one
+    fn read_nodes<'de, R: XmlSource<'de>>(reader: &mut Reader<R>, parent: Option<&str>) -> Result<Vec<XmlNode>, PError> {
+        let mut nodes = vec![];
+        let mut buffer = Vec::new();
+        loop {
+            buffer.clear();
+            match reader.read_event_into(&mut buffer) {
+                Err(error) => { return Err(PError::new(&format!("Error at position {}: {:?}", reader.buffer_position(), error))) },
+                Ok(Event::Eof) => {
+                    return match parent {
+                        Some(name) => Err(PError::new(&format!("Missing end tag for '{}'", name))),
+                        None => Ok(nodes),
+                    }
+                },
+                Ok(Event::Start(event)) => {
+                    let child = Element::read(reader, &event)?;
+                    nodes.push(XmlNode::Element(child));
+                },
+                Ok(Event::Empty(event)) => {
+                    let mut child = Element::new(&String::from_utf8(event.name().0.to_vec())?);
+                    child.attributes = Element::read_attributes(&event)?;
+                    nodes.push(XmlNode::Element(child));
+                },
+                Ok(Event::End(event)) => {
+                    return match parent {
+                        Some(_) => Ok(nodes),
+                        None => Err(PError::new(&format!("Unexpected end tag '{}'", String::from_utf8_lossy(event.name().0)))),
+                    }
+                },
+                // Whitespace only text is kept too, the tree is written back as it was read
+                Ok(Event::Text(event)) => { nodes.push(XmlNode::Text(decode_text(&event)?)) },
+                Ok(Event::CData(event)) => { nodes.push(XmlNode::CData(decode_cdata(&event)?)) },
+                Ok(Event::Comment(event)) => { nodes.push(XmlNode::Comment(String::from_utf8(event.to_vec())?)) },
+                Ok(Event::PI(event)) => { nodes.push(XmlNode::PI(String::from_utf8(event.to_vec())?)) },
+                Ok(Event::Decl(_)) => {},
+                Ok(Event::DocType(_)) => {},
+            }
+        }
+    }
+
+    fn read_attributes(event: &BytesStart) -> Result<Vec<(String, String)>, PError> {
+        let mut attributes = vec![];
+        for attribute in event.attributes() {
+            let attribute = attribute?;
+            attributes.push((String::from_utf8(attribute.key.0.to_vec())?, decode_text(&attribute.value)?));
+        }
+        Ok(attributes)
+    }
+}
+
+impl<'de> XmlDeserializable<'de> for Element {
+    fn from_xml<R: XmlSource<'de>>(reader: &mut Reader<R>, start_event: Option<&BytesStart>) -> Result<Option<Self>, PError> {
+        match start_event {
+            Some(start_event) => Ok(Some(Element::read(reader, start_event)?)),
+            None => Err(PError::new("No start element to read")),
+        }
+    }
+}
+
+impl<'de> XmlDeserializable<'de> for XmlNode {
+    fn from_xml<R: XmlSource<'de>>(reader: &mut Reader<R>, start_event: Option<&BytesStart>) -> Result<Option<Self>, PError> {
+        Ok(Element::from_xml(reader, start_event)?.map(XmlNode::Element))
+    }
+}
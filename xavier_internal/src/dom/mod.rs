@@ -0,0 +1,143 @@
+mod serialize;
+mod deserialize;
+pub(crate) mod builder;
+
+/* Owned XML tree, for ad hoc documents and content no field models (other_elements) */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum XmlNode {
+    Element(Element),
+    Text(String),
+    CData(String),
+    Comment(String),
+    PI(String),
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Element {
+    pub name: String,
+    /* In document order, values are decoded */
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<XmlNode>,
+}
+
+impl XmlNode {
+    pub fn as_element(&self) -> Option<&Element> {
+        match self {
+            XmlNode::Element(element) => Some(element),
+            _ => None,
+        }
+    }
+
+    pub fn as_element_mut(&mut self) -> Option<&mut Element> {
+        match self {
+            XmlNode::Element(element) => Some(element),
+            _ => None,
+        }
+    }
+
+    /* Content of text and CDATA nodes */
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            XmlNode::Text(text) | XmlNode::CData(text) => Some(text),
+            _ => None,
+        }
+    }
+}
+
+impl From<Element> for XmlNode {
+    fn from(element: Element) -> Self {
+        XmlNode::Element(element)
+    }
+}
+
+impl Element {
+    pub fn new(name: &str) -> Self {
+        Element { name: name.to_string(), ..Element::default() }
+    }
+
+    pub fn with_attribute(mut self, name: &str, value: &str) -> Self {
+        self.set_attribute(name, value);
+        self
+    }
+
+    pub fn with_child<N: Into<XmlNode>>(mut self, child: N) -> Self {
+        self.children.push(child.into());
+        self
+    }
+
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.children.push(XmlNode::Text(text.to_string()));
+        self
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+
+    /* Replaces the value in place or appends the attribute */
+    pub fn set_attribute(&mut self, name: &str, value: &str) {
+        match self.attributes.iter_mut().find(|(key, _)| key == name) {
+            Some((_, current)) => *current = value.to_string(),
+            None => self.attributes.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    pub fn remove_attribute(&mut self, name: &str) -> Option<String> {
+        let index = self.attributes.iter().position(|(key, _)| key == name)?;
+        Some(self.attributes.remove(index).1)
+    }
+
+    /* Child elements, skipping text, comments and instructions */
+    pub fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(XmlNode::as_element)
+    }
+
+    pub fn elements_mut(&mut self) -> impl Iterator<Item = &mut Element> {
+        self.children.iter_mut().filter_map(XmlNode::as_element_mut)
+    }
+
+    /* First child element with the name */
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.elements().find(|element| element.name == name)
+    }
+
+    pub fn child_mut(&mut self, name: &str) -> Option<&mut Element> {
+        self.elements_mut().find(|element| element.name == name)
+    }
+
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.elements().filter(move |element| element.name == name)
+    }
+
+    /* Text and CDATA of the direct children joined together */
+    pub fn text(&self) -> String {
+        self.children.iter().filter_map(XmlNode::as_text).collect()
+    }
+
+    /* Replaces the children by a single text node */
+    pub fn set_text(&mut self, text: &str) {
+        self.children = vec![XmlNode::Text(text.to_string())];
+    }
+
+    pub fn push<N: Into<XmlNode>>(&mut self, child: N) {
+        self.children.push(child.into());
+    }
+
+    /* Panics if index > children.len(), like Vec::insert */
+    pub fn insert<N: Into<XmlNode>>(&mut self, index: usize, child: N) {
+        self.children.insert(index, child.into());
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<XmlNode> {
+        (index < self.children.len()).then(|| self.children.remove(index))
+    }
+
+    /* Removes the first child element with the name */
+    pub fn remove_child(&mut self, name: &str) -> Option<Element> {
+        let index = self.children.iter().position(|child| child.as_element().is_some_and(|element| element.name == name))?;
+        match self.children.remove(index) {
+            XmlNode::Element(element) => Some(element),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use std::io;
+use crate::dom::{Element, XmlNode};
+use crate::serialize::macro_trait::XmlSerializable;
+use crate::serialize::writer::XmlWriter;
+
+impl XmlSerializable for XmlNode {
+    fn write_xml(&self, writer: &mut XmlWriter, root: bool) -> io::Result<()> {
+        match self {
+            XmlNode::Element(element) => element.write_xml(writer, root),
+            XmlNode::Text(text) => writer.write_text(text),
+            XmlNode::CData(text) => writer.write_unescaped(|writer| writer.write_text(&format!("<![CDATA[{}]]>", text))),
+            XmlNode::Comment(text) => writer.write_markup(&format!("<!--{}-->", text)),
+            XmlNode::PI(text) => writer.write_markup(&format!("<?{}?>", text)),
+        }
+    }
+}
+
+impl XmlSerializable for Element {
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        writer.start_tag(&self.name)?;
+        for (name, value) in &self.attributes {
+            writer.write_attribute(name, value)?;
+        }
+        if self.children.is_empty() {
+            return writer.end_empty_tag();
+        }
+        writer.end_start_tag()?;
+        for child in &self.children {
+            child.write_xml(writer, false)?;
+        }
+        writer.end_tag(&self.name)
+    }
+}
@@ -1,3 +1,4 @@
 pub mod serialize;
 pub mod deserialize;
+pub mod dom;
 
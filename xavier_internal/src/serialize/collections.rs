@@ -1,22 +1,82 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashSet, LinkedList, VecDeque};
+use std::io;
 use crate::serialize::macro_trait::XmlSerializable;
+use crate::serialize::writer::XmlWriter;
 
-impl <T: XmlSerializable> XmlSerializable for Vec<T> {
-    fn to_xml(&self, _: bool) -> String {
-        self.iter().fold(String::new(), |mut acc, item| {
-            acc.push_str(&item.to_xml(false));
-            acc
-        })
+/* Collections written item by item, also used by the derive for #[xml(inner)] fields.
+   Deterministic output sorts the items of unordered collections (HashSet) by their serialized bytes */
+pub trait XmlItems {
+    type Item;
+    fn write_items<F>(&self, writer: &mut XmlWriter, write: F) -> io::Result<()>
+        where F: FnMut(&mut XmlWriter, &Self::Item) -> io::Result<()>;
+}
+
+macro_rules! ordered_items {
+    ($($collection:ty),*) => {
+        $(
+            impl <T> XmlItems for $collection {
+                type Item = T;
+                fn write_items<F>(&self, writer: &mut XmlWriter, mut write: F) -> io::Result<()>
+                    where F: FnMut(&mut XmlWriter, &T) -> io::Result<()> {
+                    for item in self.iter() {
+                        write(writer, item)?;
+                    }
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+ordered_items!(VecDeque<T>, LinkedList<T>, BTreeSet<T>, [T], Option<T>);
+
+impl <T> XmlItems for HashSet<T> {
+    type Item = T;
+    fn write_items<F>(&self, writer: &mut XmlWriter, mut write: F) -> io::Result<()>
+        where F: FnMut(&mut XmlWriter, &T) -> io::Result<()> {
+        if writer.options().deterministic {
+            // Items are sorted by their serialized bytes, so no Ord is needed
+            let mut items = Vec::with_capacity(self.len());
+            for item in self.iter() {
+                let mut bytes: Vec<u8> = Vec::new();
+                write(&mut XmlWriter::with_options(&mut bytes, writer.options().clone()), item)?;
+                items.push((bytes, item));
+            }
+            items.sort_by(|(left, _), (right, _)| left.cmp(right));
+            for (_, item) in items {
+                write(writer, item)?;
+            }
+        } else {
+            for item in self.iter() {
+                write(writer, item)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+macro_rules! serializable_collection {
+    ($($collection:ty),*) => {
+        $(
+            impl <T: XmlSerializable> XmlSerializable for $collection {
+                fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+                    self.write_items(writer, |writer, item| item.write_xml(writer, false))
+                }
+            }
+        )*
+    };
+}
+
+serializable_collection!(Vec<T>, VecDeque<T>, LinkedList<T>, BTreeSet<T>, [T]);
+
+impl <T: XmlSerializable> XmlSerializable for HashSet<T> {
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        self.write_items(writer, |writer, item| item.write_xml(writer, false))
     }
 }
 
-impl <T: XmlSerializable> XmlSerializable for HashMap<String, T> {
-    fn to_xml(&self, _: bool) -> String {
-        self.iter().fold(String::new(), |mut acc, item| {
-            acc.push_str(&format!("<{}>", &item.0));
-            acc.push_str(&item.1.to_xml(false));
-            acc.push_str(&format!("</{}>", &item.0));
-            acc
-        })
+impl <T: XmlSerializable, const N: usize> XmlSerializable for [T; N] {
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        self.as_slice().write_xml(writer, false)
     }
-}
\ No newline at end of file
+}
@@ -1,4 +1,6 @@
+use std::borrow::Cow;
 
+/* The macros below build encoded strings, write them through #[xml(raw)] fields or RawXml, a plain String is escaped again */
 #[macro_export]
 macro_rules! cdata {
     ($expr:expr) => { format!("<![CDATA[{}]]>", $expr).to_string() };
@@ -22,3 +24,34 @@ pub fn escape_xml(input: &str) -> String {
         .replace("\"", "&quot;")
         .replace("'", "&apos;")
 }
+
+pub fn push_escaped(buffer: &mut String, input: &str) {
+    for c in input.chars() {
+        match c {
+            '&' => buffer.push_str("&amp;"),
+            '<' => buffer.push_str("&lt;"),
+            '>' => buffer.push_str("&gt;"),
+            '"' => buffer.push_str("&quot;"),
+            '\'' => buffer.push_str("&apos;"),
+            _ => buffer.push(c),
+        }
+    }
+}
+
+/* Escapes character data, every '&', '<' and '>' is escaped so the text reads back as it was written.
+   Already encoded values (encode!, cdata!) go in #[xml(raw)] fields or RawXml to be written as they are */
+pub fn escape_text(input: &str) -> Cow<'_, str> {
+    if !input.contains(['&', '<', '>']) {
+        return Cow::Borrowed(input);
+    }
+    let mut buffer = String::with_capacity(input.len() + 16);
+    for c in input.chars() {
+        match c {
+            '&' => buffer.push_str("&amp;"),
+            '<' => buffer.push_str("&lt;"),
+            '>' => buffer.push_str("&gt;"),
+            _ => buffer.push(c),
+        }
+    }
+    Cow::Owned(buffer)
+}
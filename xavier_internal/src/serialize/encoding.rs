@@ -0,0 +1,162 @@
+use std::io;
+use std::io::Write;
+use encoding_rs::Encoding;
+
+/* Target encoding of the serialized bytes, chars the encoding can't represent become &#N; in text and attribute values */
+#[derive(Clone, Copy, Debug)]
+pub enum OutputEncoding {
+    Utf8,
+    Utf16 { big_endian: bool },
+    /* Single byte encodings where the code point is the byte (US-ASCII and ISO-8859-1) */
+    Limited { max: u32 },
+    Other(&'static Encoding),
+}
+
+impl OutputEncoding {
+    pub fn from_label(label: &str) -> io::Result<Self> {
+        let normalized = label.trim().to_ascii_uppercase().replace('_', "-");
+        // encoding_rs follows WHATWG, which maps latin1 and ascii to windows-1252 and has no UTF-16 encoder
+        let encoding = match normalized.as_str() {
+            "UTF-8" | "UTF8" => OutputEncoding::Utf8,
+            "UTF-16" | "UTF-16LE" | "UTF16" | "UTF16LE" => OutputEncoding::Utf16 { big_endian: false },
+            "UTF-16BE" | "UTF16BE" => OutputEncoding::Utf16 { big_endian: true },
+            "US-ASCII" | "ASCII" => OutputEncoding::Limited { max: 0x7F },
+            "ISO-8859-1" | "ISO8859-1" | "LATIN1" | "LATIN-1" => OutputEncoding::Limited { max: 0xFF },
+            _ => match Encoding::for_label(label.trim().as_bytes()) {
+                Some(encoding) if encoding.output_encoding() == encoding => OutputEncoding::Other(encoding),
+                _ => return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("Unsupported output encoding: {}", label))),
+            }
+        };
+        Ok(encoding)
+    }
+
+    pub fn bom(&self) -> &'static [u8] {
+        match self {
+            OutputEncoding::Utf16 { big_endian: false } => &[0xFF, 0xFE],
+            OutputEncoding::Utf16 { big_endian: true } => &[0xFE, 0xFF],
+            _ => &[],
+        }
+    }
+
+    /* Markup (names, CDATA, comments, PIs), a character reference is not allowed there */
+    pub fn write(&self, writer: &mut dyn Write, value: &str) -> io::Result<()> {
+        self.write_encoded(writer, value, false)
+    }
+
+    /* Escaped text or attribute value */
+    pub fn write_char_data(&self, writer: &mut dyn Write, value: &str) -> io::Result<()> {
+        self.write_encoded(writer, value, true)
+    }
+
+    /* Pre rendered XML, references are only used in its text and attribute values */
+    pub fn write_fragment(&self, writer: &mut dyn Write, value: &str, in_tag: bool) -> io::Result<()> {
+        if let OutputEncoding::Utf8 | OutputEncoding::Utf16 { .. } = self {
+            return self.write(writer, value);
+        }
+        let mut bytes = Vec::with_capacity(value.len());
+        for (run, references) in fragment_runs(value, in_tag) {
+            self.encode(run, references, &mut bytes)?;
+        }
+        writer.write_all(&bytes)
+    }
+
+    // UTF-8 goes straight to the writer, other encodings are transcoded into a buffer first
+    fn write_encoded(&self, writer: &mut dyn Write, value: &str, references: bool) -> io::Result<()> {
+        if let OutputEncoding::Utf8 = self {
+            return writer.write_all(value.as_bytes());
+        }
+        let mut bytes = Vec::with_capacity(value.len());
+        self.encode(value, references, &mut bytes)?;
+        writer.write_all(&bytes)
+    }
+
+    fn encode(&self, value: &str, references: bool, bytes: &mut Vec<u8>) -> io::Result<()> {
+        match self {
+            OutputEncoding::Utf8 => bytes.extend_from_slice(value.as_bytes()),
+            OutputEncoding::Utf16 { big_endian } => {
+                for unit in value.encode_utf16() {
+                    if *big_endian {
+                        bytes.extend_from_slice(&unit.to_be_bytes());
+                    } else {
+                        bytes.extend_from_slice(&unit.to_le_bytes());
+                    }
+                }
+            },
+            OutputEncoding::Limited { max } => {
+                for char in value.chars() {
+                    if char as u32 <= *max {
+                        bytes.push(char as u8);
+                    } else if references {
+                        bytes.extend_from_slice(format!("&#{};", char as u32).as_bytes());
+                    } else {
+                        return Err(unrepresentable(char));
+                    }
+                }
+            },
+            OutputEncoding::Other(encoding) => {
+                let (encoded, _, had_errors) = encoding.encode(value);
+                if had_errors && !references {
+                    let char = value.chars().find(|char| {
+                        let mut buffer = [0; 4];
+                        encoding.encode(char.encode_utf8(&mut buffer)).2
+                    });
+                    return Err(unrepresentable(char.unwrap_or(char::REPLACEMENT_CHARACTER)));
+                }
+                bytes.extend_from_slice(&encoded);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn unrepresentable(char: char) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!(
+        "Char '{}' (U+{:04X}) can't be written in the output encoding and a character reference is only allowed in text and attribute values",
+        char, char as u32))
+}
+
+// Splits a fragment in runs where references are allowed (text, attribute values) or not (names, CDATA, comments, PIs)
+fn fragment_runs(value: &str, in_tag: bool) -> Vec<(&str, bool)> {
+    let mut runs = vec![];
+    let mut rest = value;
+    let mut in_tag = in_tag;
+    while !rest.is_empty() {
+        if in_tag {
+            match rest.find(['"', '\'', '>']) {
+                Some(index) if rest.as_bytes()[index] == b'>' => {
+                    runs.push((&rest[..=index], false));
+                    rest = &rest[index + 1..];
+                    in_tag = false;
+                },
+                Some(index) => {
+                    runs.push((&rest[..=index], false));
+                    let quote = rest.as_bytes()[index] as char;
+                    rest = &rest[index + 1..];
+                    let end = rest.find(quote).map(|end| end + 1).unwrap_or(rest.len());
+                    runs.push((&rest[..end], true));
+                    rest = &rest[end..];
+                },
+                None => {
+                    runs.push((rest, false));
+                    rest = "";
+                }
+            }
+        } else {
+            let index = rest.find('<').unwrap_or(rest.len());
+            runs.push((&rest[..index], true));
+            rest = &rest[index..];
+            let section = [("<![CDATA[", "]]>"), ("<!--", "-->"), ("<?", "?>")]
+                .into_iter()
+                .find(|(open, _)| rest.starts_with(open));
+            match section {
+                Some((open, close)) => {
+                    let end = rest[open.len()..].find(close).map(|end| open.len() + end + close.len()).unwrap_or(rest.len());
+                    runs.push((&rest[..end], false));
+                    rest = &rest[end..];
+                },
+                None => in_tag = !rest.is_empty(),
+            }
+        }
+    }
+    runs
+}
@@ -1,5 +1,13 @@
+use std::io;
+use crate::serialize::writer::XmlWriter;
 
 pub trait XmlSerializable {
-    fn to_xml(&self, root: bool) -> String;
-}
+    fn write_xml(&self, writer: &mut XmlWriter, root: bool) -> io::Result<()>;
 
+    /* Panics when write_xml fails, a RefCell already mutably borrowed or a poisoned Mutex/RwLock */
+    fn to_xml(&self, root: bool) -> String {
+        let mut buffer: Vec<u8> = Vec::new();
+        self.write_xml(&mut XmlWriter::new(&mut buffer), root).expect("Writing XML to memory must not fail");
+        String::from_utf8(buffer).expect("XML writer must produce UTF-8")
+    }
+}
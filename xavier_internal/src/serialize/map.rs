@@ -0,0 +1,98 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::io;
+use crate::serialize::macro_trait::XmlSerializable;
+use crate::serialize::text::ToXmlText;
+use crate::serialize::writer::XmlWriter;
+
+/* Maps usable with the entry layout: <entry key="k">v</entry> */
+pub trait XmlMap {
+    type Key;
+    type Value;
+    /* Entries already come in key order, deterministic output only sorts maps without one (HashMap) */
+    const ORDERED: bool = false;
+    fn entries(&self) -> impl Iterator<Item = (&Self::Key, &Self::Value)>;
+    fn insert_entry(&mut self, key: Self::Key, value: Self::Value);
+}
+
+impl<K: Eq + Hash, V> XmlMap for HashMap<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn entries(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.iter()
+    }
+
+    fn insert_entry(&mut self, key: K, value: V) {
+        self.insert(key, value);
+    }
+}
+
+impl<K: Ord, V> XmlMap for BTreeMap<K, V> {
+    type Key = K;
+    type Value = V;
+    const ORDERED: bool = true;
+
+    fn entries(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.iter()
+    }
+
+    fn insert_entry(&mut self, key: K, value: V) {
+        self.insert(key, value);
+    }
+}
+
+impl <K: ToXmlText + Eq + Hash, T: XmlSerializable> XmlSerializable for HashMap<K, T> {
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        write_named_entries(writer, self)
+    }
+}
+
+impl <K: ToXmlText + Ord, T: XmlSerializable> XmlSerializable for BTreeMap<K, T> {
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        write_named_entries(writer, self)
+    }
+}
+
+/* Element name as key: <key>value</key> */
+pub fn write_named_entries<M>(writer: &mut XmlWriter, map: &M) -> io::Result<()>
+    where M: XmlMap, M::Key: ToXmlText, M::Value: XmlSerializable {
+    for_each_entry(writer, map, |writer, entry_key, value| {
+        let entry_key = entry_key.to_xml_text();
+        writer.start_tag(&entry_key)?;
+        writer.end_start_tag()?;
+        value.write_xml(writer, false)?;
+        writer.end_tag(&entry_key)
+    })
+}
+
+pub fn write_entries<M>(writer: &mut XmlWriter, map: &M, entry: &str, key: &str) -> io::Result<()>
+    where M: XmlMap, M::Key: ToXmlText, M::Value: XmlSerializable {
+    for_each_entry(writer, map, |writer, entry_key, value| {
+        writer.start_tag(entry)?;
+        writer.write_attribute(key, entry_key.to_xml_text().as_ref())?;
+        writer.end_start_tag()?;
+        value.write_xml(writer, false)?;
+        writer.end_tag(entry)
+    })
+}
+
+// Deterministic output sorts unordered maps by the written key so they come out the same on every run
+fn for_each_entry<M, F>(writer: &mut XmlWriter, map: &M, mut write: F) -> io::Result<()>
+    where M: XmlMap, M::Key: ToXmlText, F: FnMut(&mut XmlWriter, &M::Key, &M::Value) -> io::Result<()> {
+    if writer.options().deterministic && !M::ORDERED {
+        let mut entries: Vec<(Cow<str>, &M::Key, &M::Value)> = map.entries()
+            .map(|(entry_key, value)| (entry_key.to_xml_text(), entry_key, value))
+            .collect();
+        entries.sort_by(|(left, _, _), (right, _, _)| left.cmp(right));
+        for (_, entry_key, value) in entries {
+            write(writer, entry_key, value)?;
+        }
+    } else {
+        for (entry_key, value) in map.entries() {
+            write(writer, entry_key, value)?;
+        }
+    }
+    Ok(())
+}
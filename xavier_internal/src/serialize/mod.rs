@@ -1,6 +1,13 @@
 pub mod namespaces;
 pub mod encode;
+pub mod encoding;
 pub mod primitives;
 pub mod macro_trait;
 pub mod collections;
+pub mod map;
+pub mod text;
+pub mod writer;
+pub mod options;
+pub mod raw;
 mod option;
+mod wrappers;
@@ -1,11 +1,13 @@
+use std::io;
 use crate::serialize::macro_trait::XmlSerializable;
+use crate::serialize::writer::XmlWriter;
 
 impl <T: XmlSerializable> XmlSerializable for Option<T> {
-    fn to_xml(&self, _: bool) -> String {
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
         if let Some(value) = &self {
-            value.to_xml(false)
+            value.write_xml(writer, false)
         } else {
-            "".to_string()
+            Ok(())
         }
     }
 }
@@ -0,0 +1,50 @@
+#[derive(Clone, Debug)]
+pub struct SerializeOptions {
+    /* None keeps everything on a single line */
+    pub indent: Option<String>,
+    pub line_ending: String,
+    /* Pretty mode only: attributes that would cross this column go to their own line */
+    pub max_line_width: Option<usize>,
+    /* Map entries sorted by key and attributes sorted by name, equal values always give the same bytes */
+    pub deterministic: bool,
+}
+
+impl Default for SerializeOptions {
+    fn default() -> Self {
+        SerializeOptions { indent: None, line_ending: "\n".to_string(), max_line_width: None, deterministic: false }
+    }
+}
+
+impl SerializeOptions {
+    pub fn compact() -> Self {
+        SerializeOptions::default()
+    }
+
+    pub fn pretty() -> Self {
+        SerializeOptions { indent: Some("  ".to_string()), ..SerializeOptions::default() }
+    }
+
+    pub fn indent(mut self, indent: &str) -> Self {
+        self.indent = Some(indent.to_string());
+        self
+    }
+
+    pub fn line_ending(mut self, line_ending: &str) -> Self {
+        self.line_ending = line_ending.to_string();
+        self
+    }
+
+    pub fn wrap_attributes(mut self, max_line_width: usize) -> Self {
+        self.max_line_width = Some(max_line_width);
+        self
+    }
+
+    pub fn deterministic(mut self) -> Self {
+        self.deterministic = true;
+        self
+    }
+
+    pub fn is_pretty(&self) -> bool {
+        self.indent.is_some()
+    }
+}
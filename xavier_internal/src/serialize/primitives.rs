@@ -1,102 +1,110 @@
+use std::io;
 use crate::serialize::macro_trait::XmlSerializable;
+use crate::serialize::writer::XmlWriter;
 
 impl XmlSerializable for i8 {
-    fn to_xml(&self, _: bool) -> String {
-        self.to_string()
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        writer.write_display(self)
     }
 }
 
 impl XmlSerializable for i16 {
-    fn to_xml(&self, _: bool) -> String {
-        self.to_string()
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        writer.write_display(self)
     }
 }
 
 impl XmlSerializable for i32 {
-    fn to_xml(&self, _: bool) -> String {
-        self.to_string()
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        writer.write_display(self)
     }
 }
 
 impl XmlSerializable for i64 {
-    fn to_xml(&self, _: bool) -> String {
-        self.to_string()
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        writer.write_display(self)
     }
 }
 
 impl XmlSerializable for i128 {
-    fn to_xml(&self, _: bool) -> String {
-        self.to_string()
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        writer.write_display(self)
     }
 }
 
 impl XmlSerializable for u8 {
-    fn to_xml(&self, _: bool) -> String {
-        self.to_string()
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        writer.write_display(self)
     }
 }
 
 impl XmlSerializable for u16 {
-    fn to_xml(&self, _: bool) -> String {
-        self.to_string()
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        writer.write_display(self)
     }
 }
 
 impl XmlSerializable for u32 {
-    fn to_xml(&self, _: bool) -> String {
-        self.to_string()
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        writer.write_display(self)
     }
 }
 
 impl XmlSerializable for u64 {
-    fn to_xml(&self, _: bool) -> String {
-        self.to_string()
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        writer.write_display(self)
     }
 }
 
 impl XmlSerializable for u128 {
-    fn to_xml(&self, _: bool) -> String {
-        self.to_string()
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        writer.write_display(self)
     }
 }
 impl XmlSerializable for f32 {
-    fn to_xml(&self, _: bool) -> String {
-        self.to_string()
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        writer.write_display(self)
     }
 }
 
 impl XmlSerializable for f64 {
-    fn to_xml(&self, _: bool) -> String {
-        self.to_string()
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        writer.write_display(self)
     }
 }
 
 impl XmlSerializable for bool {
-    fn to_xml(&self, _: bool) -> String {
-        self.to_string()
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        writer.write_display(self)
     }
 }
 
 impl XmlSerializable for String {
-    fn to_xml(&self, _: bool) -> String {
-        self.to_string()
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        writer.write_text(self)
+    }
+}
+
+impl XmlSerializable for str {
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        writer.write_text(self)
     }
 }
 
 impl XmlSerializable for isize {
-    fn to_xml(&self, _: bool) -> String {
-        self.to_string()
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        writer.write_display(self)
     }
 }
 
 impl XmlSerializable for usize {
-    fn to_xml(&self, _: bool) -> String {
-        self.to_string()
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        writer.write_display(self)
     }
 }
 
 impl XmlSerializable for char {
-    fn to_xml(&self, _: bool) -> String { format!("{}", self) }
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> { writer.write_display(self) }
 }
 
 
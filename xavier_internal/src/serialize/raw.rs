@@ -0,0 +1,13 @@
+use std::io;
+use crate::serialize::macro_trait::XmlSerializable;
+use crate::serialize::writer::XmlWriter;
+
+/* Pre rendered XML fragment, written without any escaping */
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RawXml(pub String);
+
+impl XmlSerializable for RawXml {
+    fn write_xml(&self, writer: &mut XmlWriter, _: bool) -> io::Result<()> {
+        writer.write_raw(&self.0)
+    }
+}
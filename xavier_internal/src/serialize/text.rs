@@ -0,0 +1,42 @@
+use std::borrow::Cow;
+
+/* Text form of a value used as a map key, derived for unit enums from the variant names */
+pub trait ToXmlText {
+    fn to_xml_text(&self) -> Cow<'_, str>;
+}
+
+macro_rules! display_text {
+    ($($ty:ty),*) => {
+        $(impl ToXmlText for $ty {
+            fn to_xml_text(&self) -> Cow<'_, str> {
+                Cow::Owned(self.to_string())
+            }
+        })*
+    };
+}
+
+display_text!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char);
+
+impl ToXmlText for str {
+    fn to_xml_text(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl ToXmlText for String {
+    fn to_xml_text(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl ToXmlText for Cow<'_, str> {
+    fn to_xml_text(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<T: ToXmlText + ?Sized> ToXmlText for &T {
+    fn to_xml_text(&self) -> Cow<'_, str> {
+        (**self).to_xml_text()
+    }
+}
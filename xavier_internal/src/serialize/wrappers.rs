@@ -0,0 +1,54 @@
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, RwLock};
+use crate::serialize::macro_trait::XmlSerializable;
+use crate::serialize::writer::XmlWriter;
+
+macro_rules! serializable_pointer {
+    ($($pointer:ident),*) => {
+        $(
+            impl <T: XmlSerializable + ?Sized> XmlSerializable for $pointer<T> {
+                fn write_xml(&self, writer: &mut XmlWriter, root: bool) -> io::Result<()> {
+                    (**self).write_xml(writer, root)
+                }
+            }
+        )*
+    };
+}
+
+serializable_pointer!(Box, Rc, Arc);
+
+impl <T: XmlSerializable + ?Sized> XmlSerializable for &T {
+    fn write_xml(&self, writer: &mut XmlWriter, root: bool) -> io::Result<()> {
+        (**self).write_xml(writer, root)
+    }
+}
+
+impl <B: XmlSerializable + ToOwned + ?Sized> XmlSerializable for Cow<'_, B> {
+    fn write_xml(&self, writer: &mut XmlWriter, root: bool) -> io::Result<()> {
+        self.as_ref().write_xml(writer, root)
+    }
+}
+
+impl <T: XmlSerializable + ?Sized> XmlSerializable for RefCell<T> {
+    fn write_xml(&self, writer: &mut XmlWriter, root: bool) -> io::Result<()> {
+        let value = self.try_borrow().map_err(|_| io::Error::other("RefCell is mutably borrowed"))?;
+        value.write_xml(writer, root)
+    }
+}
+
+impl <T: XmlSerializable + ?Sized> XmlSerializable for Mutex<T> {
+    fn write_xml(&self, writer: &mut XmlWriter, root: bool) -> io::Result<()> {
+        let value = self.lock().map_err(|_| io::Error::other("Mutex is poisoned"))?;
+        value.write_xml(writer, root)
+    }
+}
+
+impl <T: XmlSerializable + ?Sized> XmlSerializable for RwLock<T> {
+    fn write_xml(&self, writer: &mut XmlWriter, root: bool) -> io::Result<()> {
+        let value = self.read().map_err(|_| io::Error::other("RwLock is poisoned"))?;
+        value.write_xml(writer, root)
+    }
+}
@@ -0,0 +1,394 @@
+use std::fmt;
+use std::fmt::Display;
+use std::io;
+use std::io::Write;
+use crate::dom::builder::TreeBuilder;
+use crate::dom::Element;
+use crate::deserialize::error::PError;
+use crate::serialize::encode::{escape_text, push_escaped};
+use crate::serialize::encoding::OutputEncoding;
+use crate::serialize::macro_trait::XmlSerializable;
+use crate::serialize::options::SerializeOptions;
+
+struct ElementState {
+    has_children: bool,
+    has_text: bool,
+    /* Prefixes bound by xmlns:prefix attributes of the element */
+    prefixes: Vec<String>,
+}
+
+/* Sink used by the serializers, everything is written straight to the inner writer */
+pub struct XmlWriter<'a> {
+    /* None for the tree writer, it has nothing to write to */
+    inner: Option<&'a mut dyn Write>,
+    options: SerializeOptions,
+    elements: Vec<ElementState>,
+    in_attribute: bool,
+    /* Between start_tag and the end of the start tag, raw fragments there are attributes */
+    in_start_tag: bool,
+    unescaped: bool,
+    attribute_value: String,
+    /* Deterministic mode only: attributes of the open start tag, written sorted when it closes */
+    pending_attributes: Vec<(String, String)>,
+    column: usize,
+    started: bool,
+    /* Only byte oriented writers follow the declared encoding, strings are always UTF-8 */
+    transcode: bool,
+    encoding: OutputEncoding,
+    /* Set by XmlWriter::tree, nodes are built instead of written */
+    tree: Option<TreeBuilder>,
+}
+
+impl<'a> XmlWriter<'a> {
+    pub fn new(inner: &'a mut dyn Write) -> Self {
+        XmlWriter::with_options(inner, SerializeOptions::default())
+    }
+
+    pub fn with_options(inner: &'a mut dyn Write, options: SerializeOptions) -> Self {
+        XmlWriter::with_sink(Some(inner), options)
+    }
+
+    /* Writer that transcodes the output to the encoding declared by the root element */
+    pub fn transcoding(inner: &'a mut dyn Write, options: SerializeOptions) -> Self {
+        XmlWriter { transcode: true, ..XmlWriter::with_options(inner, options) }
+    }
+
+    /* Writer that builds the DOM tree of what is written, only pre rendered fragments are parsed */
+    pub fn tree() -> XmlWriter<'static> {
+        XmlWriter { tree: Some(TreeBuilder::default()), ..XmlWriter::with_sink(None, SerializeOptions::default()) }
+    }
+
+    /* Root element of a tree writer */
+    pub fn into_tree(self) -> Option<Element> {
+        self.tree.and_then(TreeBuilder::finish)
+    }
+
+    fn with_sink(inner: Option<&'a mut dyn Write>, options: SerializeOptions) -> Self {
+        XmlWriter {
+            inner,
+            options,
+            elements: vec![],
+            in_attribute: false,
+            in_start_tag: false,
+            unescaped: false,
+            attribute_value: String::new(),
+            pending_attributes: vec![],
+            column: 0,
+            started: false,
+            transcode: false,
+            encoding: OutputEncoding::Utf8,
+            tree: None,
+        }
+    }
+
+    pub fn set_encoding(&mut self, label: &str) -> io::Result<()> {
+        if !self.transcode || self.started {
+            return Ok(());
+        }
+        self.encoding = OutputEncoding::from_label(label)?;
+        match &mut self.inner {
+            Some(inner) => inner.write_all(self.encoding.bom()),
+            None => Ok(()),
+        }
+    }
+
+    pub fn options(&self) -> &SerializeOptions {
+        &self.options
+    }
+
+    /* Inline content written as is (namespaces, pre encoded text...) */
+    pub fn write_raw(&mut self, value: &str) -> io::Result<()> {
+        if self.in_start_tag && !self.in_attribute {
+            for (index, _) in value.match_indices("xmlns:") {
+                let prefix = &value[index + 6..];
+                let end = prefix.find(|c: char| c == '=' || c.is_whitespace()).unwrap_or(prefix.len());
+                self.bind_prefix(&prefix[..end]);
+            }
+        }
+        self.put_fragment(value)
+    }
+
+    /* Writes xmlns:prefix on the open start tag unless the prefix is already bound in scope */
+    pub fn declare_namespace(&mut self, prefix: &str, uri: &str) -> io::Result<()> {
+        let declared = self.elements.iter().any(|element| element.prefixes.iter().any(|bound| bound == prefix));
+        if declared {
+            return Ok(());
+        }
+        self.write_attribute(&format!("xmlns:{}", prefix), uri)
+    }
+
+    /* Standalone markup like declarations, DTDs and processing instructions */
+    pub fn write_markup(&mut self, value: &str) -> io::Result<()> {
+        if value.is_empty() {
+            return Ok(());
+        }
+        self.break_line()?;
+        self.mark_child();
+        if let Some(tree) = &mut self.tree {
+            tree.fragment(value, false).map_err(invalid_data)?;
+        }
+        self.put(value)
+    }
+
+    pub fn write_text(&mut self, value: &str) -> io::Result<()> {
+        if !value.is_empty() {
+            self.mark_text();
+        }
+        self.put_text(value)
+    }
+
+    /* Text written inside the closure is emitted as is, used for pre rendered fragments */
+    pub fn write_unescaped<F>(&mut self, write: F) -> io::Result<()>
+        where F: FnOnce(&mut XmlWriter<'a>) -> io::Result<()> {
+        let unescaped = self.unescaped;
+        self.unescaped = true;
+        let result = write(self);
+        self.unescaped = unescaped;
+        result
+    }
+
+    /* Mixed content follows, the element is kept off the indentation even before its first text */
+    pub fn begin_mixed(&mut self) {
+        self.mark_text();
+    }
+
+    pub fn write_display<T: Display + ?Sized>(&mut self, value: &T) -> io::Result<()> {
+        self.mark_text();
+        let mut adapter = FmtAdapter { writer: self, error: None };
+        match fmt::write(&mut adapter, format_args!("{}", value)) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(adapter.error.unwrap_or_else(|| io::Error::other("Formatter error"))),
+        }
+    }
+
+    pub fn start_tag(&mut self, name: &str) -> io::Result<()> {
+        self.break_line()?;
+        self.mark_child();
+        self.elements.push(ElementState { has_children: false, has_text: false, prefixes: vec![] });
+        if let Some(tree) = &mut self.tree {
+            tree.start(name);
+        }
+        self.in_start_tag = true;
+        self.put("<")?;
+        self.put(name)
+    }
+
+    pub fn write_attribute<T: XmlSerializable + ?Sized>(&mut self, name: &str, value: &T) -> io::Result<()> {
+        if let Some(prefix) = name.strip_prefix("xmlns:") {
+            self.bind_prefix(prefix);
+        }
+        self.attribute_value.clear();
+        self.in_attribute = true;
+        let result = value.write_xml(self, false);
+        self.in_attribute = false;
+        result?;
+
+        if self.options.deterministic {
+            self.pending_attributes.push((name.to_string(), self.attribute_value.clone()));
+            return Ok(());
+        }
+        let attribute_value = std::mem::take(&mut self.attribute_value);
+        self.put_attribute(name, &attribute_value)?;
+        self.attribute_value = attribute_value;
+        Ok(())
+    }
+
+    pub fn end_start_tag(&mut self) -> io::Result<()> {
+        self.flush_attributes()?;
+        self.in_start_tag = false;
+        self.put(">")
+    }
+
+    pub fn end_empty_tag(&mut self) -> io::Result<()> {
+        self.flush_attributes()?;
+        self.in_start_tag = false;
+        self.elements.pop();
+        if let Some(tree) = &mut self.tree {
+            tree.end();
+        }
+        self.put("/>")
+    }
+
+    pub fn end_tag(&mut self, name: &str) -> io::Result<()> {
+        if let Some(element) = self.elements.pop() {
+            if element.has_children && !element.has_text {
+                self.break_line()?;
+            }
+        }
+        if let Some(tree) = &mut self.tree {
+            tree.end();
+        }
+        self.put("</")?;
+        self.put(name)?;
+        self.put(">")
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        match &mut self.inner {
+            Some(inner) => inner.flush(),
+            None => Ok(()),
+        }
+    }
+
+    fn put_attribute(&mut self, name: &str, value: &str) -> io::Result<()> {
+        if let Some(tree) = &mut self.tree {
+            return tree.attribute(name, value).map_err(invalid_data);
+        }
+        if self.should_wrap(name.chars().count() + value.chars().count() + 4) {
+            self.new_line(self.elements.len())?;
+        } else {
+            self.put(" ")?;
+        }
+        self.put(name)?;
+        self.put("=\"")?;
+        self.emit_char_data(value)?;
+        self.put("\"")
+    }
+
+    fn flush_attributes(&mut self) -> io::Result<()> {
+        if self.pending_attributes.is_empty() {
+            return Ok(());
+        }
+        let mut attributes = std::mem::take(&mut self.pending_attributes);
+        attributes.sort_by(|(left, _), (right, _)| left.cmp(right));
+        for (name, value) in &attributes {
+            self.put_attribute(name, value)?;
+        }
+        attributes.clear();
+        self.pending_attributes = attributes;
+        Ok(())
+    }
+
+    fn bind_prefix(&mut self, prefix: &str) {
+        if let Some(element) = self.elements.last_mut() {
+            element.prefixes.push(prefix.to_string());
+        }
+    }
+
+    fn mark_child(&mut self) {
+        if let Some(parent) = self.elements.last_mut() {
+            parent.has_children = true;
+        }
+    }
+
+    fn mark_text(&mut self) {
+        if self.in_attribute {
+            return;
+        }
+        if let Some(parent) = self.elements.last_mut() {
+            parent.has_text = true;
+        }
+    }
+
+    // Whitespace inside mixed content would change the text, so it is never indented
+    fn break_line(&mut self) -> io::Result<()> {
+        if !self.started || !self.options.is_pretty() || self.elements.iter().any(|element| element.has_text) {
+            return Ok(());
+        }
+        self.new_line(self.elements.len())
+    }
+
+    fn new_line(&mut self, depth: usize) -> io::Result<()> {
+        let line_ending = self.options.line_ending.clone();
+        self.emit(&line_ending)?;
+        if let Some(indent) = self.options.indent.clone() {
+            for _ in 0..depth {
+                self.emit(&indent)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn should_wrap(&self, width: usize) -> bool {
+        match self.options.max_line_width {
+            Some(max_line_width) => self.options.is_pretty() && self.column + width > max_line_width,
+            None => false,
+        }
+    }
+
+    fn put_text(&mut self, value: &str) -> io::Result<()> {
+        if self.in_attribute || self.unescaped {
+            self.put_fragment(value)
+        } else if let Some(tree) = &mut self.tree {
+            tree.text(value);
+            Ok(())
+        } else {
+            self.emit_char_data(&escape_text(value))
+        }
+    }
+
+    fn put_fragment(&mut self, value: &str) -> io::Result<()> {
+        if self.in_attribute {
+            push_escaped(&mut self.attribute_value, value);
+            Ok(())
+        } else {
+            self.emit_fragment(value)
+        }
+    }
+
+    fn put(&mut self, value: &str) -> io::Result<()> {
+        if self.in_attribute {
+            push_escaped(&mut self.attribute_value, value);
+            Ok(())
+        } else {
+            self.emit(value)
+        }
+    }
+
+    fn emit(&mut self, value: &str) -> io::Result<()> {
+        if let (true, Some(inner)) = (self.track(value), self.inner.as_deref_mut()) {
+            self.encoding.write(inner, value)?;
+        }
+        Ok(())
+    }
+
+    fn emit_char_data(&mut self, value: &str) -> io::Result<()> {
+        if let (true, Some(inner)) = (self.track(value), self.inner.as_deref_mut()) {
+            self.encoding.write_char_data(inner, value)?;
+        }
+        Ok(())
+    }
+
+    fn emit_fragment(&mut self, value: &str) -> io::Result<()> {
+        if let Some(tree) = &mut self.tree {
+            return tree.fragment(value, self.in_start_tag).map_err(invalid_data);
+        }
+        if let (true, Some(inner)) = (self.track(value), self.inner.as_deref_mut()) {
+            self.encoding.write_fragment(inner, value, self.in_start_tag)?;
+        }
+        Ok(())
+    }
+
+    // Updates the line state before a write, false when there is nothing to write
+    fn track(&mut self, value: &str) -> bool {
+        if value.is_empty() || self.tree.is_some() {
+            return false;
+        }
+        self.started = true;
+        if self.options.max_line_width.is_some() {
+            match value.rfind('\n') {
+                Some(index) => self.column = value[index + 1..].chars().count(),
+                None => self.column += value.chars().count(),
+            }
+        }
+        true
+    }
+}
+
+fn invalid_data(error: PError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+}
+
+struct FmtAdapter<'w, 'a> {
+    writer: &'w mut XmlWriter<'a>,
+    error: Option<io::Error>,
+}
+
+impl fmt::Write for FmtAdapter<'_, '_> {
+    fn write_str(&mut self, value: &str) -> fmt::Result {
+        self.writer.put_text(value).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}